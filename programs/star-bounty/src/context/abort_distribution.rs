@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::events::DistributionAborted;
+use crate::state::{Creator, DistributionPhase, DistributionState, GlobalConfig};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AbortDistribution<'info> {
+    #[account(address = global_config.admin @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        mut,
+        constraint = distribution_state.phase != DistributionPhase::Idle @ ErrorCode::NoDistributionInProgress,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+}
+
+impl<'info> AbortDistribution<'info> {
+    /// # Abort Distribution
+    ///
+    /// Closes an open epoch that can't be finalized, for instance because a page keeps failing.
+    /// Admin only, and allowed while paused. What the pages already paid stays paid. Once tallied,
+    /// the unpaid part of the investor amount is refunded to the reserve. The rest of the epoch
+    /// fees, and all of them before that, go back to `pending_fees` for the next distribution.
+    pub fn abort_distribution(&mut self) -> Result<DistributionAborted> {
+        let state = &mut self.distribution_state;
+
+        let (investor_reserve, refunded) = match state.phase {
+            DistributionPhase::Paying => {
+                // Transfer fees the pages sent on top of the shares came out of the treasury part
+                let fees_paid = state.distributed
+                    .checked_add(state.transfer_fees)
+                    .and_then(|x| x.checked_sub(state.settled))
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                let refunded = state.claimed
+                    .checked_add(state.investor_reserve)
+                    .and_then(|x| x.checked_sub(state.owed))
+                    .and_then(|x| x.checked_sub(fees_paid))
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                (state.owed - state.settled, refunded)
+            }
+            _ => (state.investor_reserve, state.claimed),
+        };
+
+        state.investor_reserve = investor_reserve;
+        state.pending_fees = state.pending_fees.checked_add(refunded).ok_or(ProgramError::ArithmeticOverflow)?;
+        state.tallied_pages = [0; 64];
+        state.paid_pages = [0; 64];
+        state.phase = DistributionPhase::Idle;
+
+        Ok(DistributionAborted {
+            launch: self.creator.key(),
+            epoch: state.epoch,
+            distributed: state.distributed,
+            investor_reserve,
+            pending_fees: state.pending_fees,
+        })
+    }
+}
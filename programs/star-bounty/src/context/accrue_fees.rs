@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::context::investor_position::*;
+use crate::events::{CreatorRemainder, DistributionEvents, FeesClaimed, FeesDistributed};
+use crate::position::distribution_skipped;
+use crate::state::{DistributionMode, DistributionPhase, DistributionPolicy, DistributionState, FeeIndex, FeeLedger, GlobalConfig, LaunchConfig};
+use crate::token::transfer_signed;
+use crate::transfer_fee::inverse_transfer_fee;
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccrueFees<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        constraint = global_config.cranker.map_or(true, |cranker| cranker == signer.key()) @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub investor_position: InvestorPosition<'info>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        constraint = distribution_policy.mode == DistributionMode::Pull @ ErrorCode::InvalidDistributionMode,
+        seeds = [
+            b"distribution_policy",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    // A push epoch opened before switching to pull has to be finalized first
+    #[account(
+        mut,
+        constraint = distribution_state.phase == DistributionPhase::Idle @ ErrorCode::DistributionInProgress,
+        seeds = [
+            b"distribution",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Account<'info, DistributionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_ledger",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = fee_ledger.bump[0],
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_index",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = fee_index.bump[0],
+    )]
+    pub fee_index: Account<'info, FeeIndex>,
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == investor_position.mint_b.key() @ ErrorCode::InvalidTreasury,
+        constraint = creator_token_account.owner == launch_config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl<'info> AccrueFees<'info> {
+    pub fn claim(&mut self) -> Result<FeesClaimed> {
+        self.investor_position.claim(&mut self.distribution_state, &mut self.fee_ledger)
+    }
+
+    /// # Accrue Fees
+    ///
+    /// Pull mode crank: the fees claimed since the last accrual are split between investors and the
+    /// treasury as in `claim_fees`. It only reads `FeeIndex`, whatever the number of streams: the
+    /// locked share is the sum of the checkpoint weights and Y0 leaves out the cancelled streams the
+    /// checkpoints saw. Every stream needs a registered checkpoint first. The investor part bumps
+    /// `fee_per_locked_unit` and stays in the position owner account until it's claimed with
+    /// `claim_investor_fees`, the rest goes to the treasury right away.
+    pub fn accrue_fees(&mut self) -> Result<DistributionEvents> {
+        require!(
+            self.fee_index.registered == self.investor_position.creator.streams,
+            ErrorCode::StreamNotRegistered
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+
+        // Fails within the cooldown, the fees wait for the next distribution if there's only dust
+        if let Some(skipped) = distribution_skipped(
+            &mut self.investor_position.creator,
+            &self.distribution_policy,
+            &self.distribution_state,
+            now,
+        )? {
+            return Ok(DistributionEvents { skipped: Some(skipped), ..Default::default() });
+        }
+
+        let reserve = self.distribution_state.investor_reserve;
+        let new_fees = self.distribution_state.pending_fees;
+
+        let epoch = self.distribution_state.epoch.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.distribution_state.epoch = epoch;
+        self.distribution_state.pending_fees = 0;
+        self.fee_ledger.record_epoch(now)?;
+
+        let total_weight = self.fee_index.total_weight;
+
+        let quote = self.distribution_policy.investor_quote(
+            new_fees,
+            total_weight,
+            self.launch_config.initial_locked(self.fee_index.cancelled)?,
+        )?;
+        let owed = self.distribution_policy.investor_owed(quote, reserve)?;
+        let distributable = self.distribution_policy.investor_distributable(owed);
+
+        // Round the index down and only book what the checkpoints can actually claim
+        let mut accrued: u64 = 0;
+        if total_weight > 0 {
+            let delta = (distributable as u128)
+                .checked_mul(FeeIndex::PRECISION)
+                .map(|x| x / total_weight as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            self.fee_index.fee_per_locked_unit = self.fee_index.fee_per_locked_unit
+                .checked_add(delta)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            accrued = self.fee_index.owed(self.fee_index.fee_per_locked_unit - delta, total_weight)?;
+            self.fee_index.unclaimed = self.fee_index.unclaimed
+                .checked_add(accrued)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        let investor_reserve = self.distribution_policy.investor_reserve(owed, accrued, reserve, total_weight);
+        self.distribution_state.investor_reserve = investor_reserve;
+
+        let treasury_part = new_fees
+            .checked_add(reserve)
+            .and_then(|x| x.checked_sub(accrued))
+            .and_then(|x| x.checked_sub(investor_reserve))
+            .and_then(|x| x.checked_add(self.fee_index.transfer_fee_budget))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // The claims send their transfer fee on top, keep it back for all the unclaimed fees
+        let transfer_fee_budget = std::cmp::min(
+            inverse_transfer_fee(&self.investor_position.mint_b.to_account_info(), self.fee_index.unclaimed)?,
+            treasury_part,
+        );
+        self.fee_index.transfer_fee_budget = transfer_fee_budget;
+        let remainder = treasury_part - transfer_fee_budget;
+
+        let mut events = DistributionEvents::default();
+
+        if remainder > 0 {
+            let signer_seeds: [&[&[u8]]; 1] = [&[
+                b"investor_fee_pos_owner".as_ref(),
+                self.investor_position.mint_b.to_account_info().key.as_ref(),
+                &self.investor_position.creator.position_owner_bump
+            ]];
+
+            transfer_signed(
+                &self.investor_position.token_b_program,
+                &self.investor_position.mint_b,
+                self.investor_position.token_b_account.to_account_info(),
+                self.creator_token_account.to_account_info(),
+                self.investor_position.investor_fee_pos_owner.to_account_info(),
+                &signer_seeds,
+                remainder,
+            )?;
+
+            events.creator_remainder = Some(CreatorRemainder {
+                launch: self.investor_position.creator.key(),
+                epoch,
+                treasury: self.creator_token_account.key(),
+                amount: remainder,
+            });
+        }
+
+        // Investor fees are recorded as they're claimed
+        self.fee_ledger.record_payout(0, remainder, 0)?;
+
+        events.distributed = Some(FeesDistributed {
+            launch: self.investor_position.creator.key(),
+            epoch,
+            fees: new_fees,
+            distributed: accrued,
+            treasury_amount: remainder,
+            investor_reserve,
+        });
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use cp_amm::{const_pda::pool_authority::ID as POOL_AUTHORITY_ID, constants::seeds::{CUSTOMIZABLE_POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX, TOKEN_VAULT_PREFIX}, max_key, min_key};
+
+use crate::events::{CreatorPositionFeesClaimed, TeamFeePayout};
+use crate::state::{Creator, CreatorFeeSplit, GlobalConfig, LaunchConfig};
+use crate::token::transfer_signed;
+use crate::position::claim_position_fees;
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimCreatorPositionFees<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        constraint = global_config.cranker.map_or(true, |cranker| cranker == signer.key()) @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mint::token_program = token_a_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_b_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        seeds = [
+            b"creator_fee_split",
+            creator.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: Loaded in `claim_creator_position_fees` if `set_creator_fee_split` created it, without a split everything goes to the treasury
+    pub creator_fee_split: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = treasury_token_a.mint == mint_a.key() @ ErrorCode::InvalidTreasury,
+        constraint = treasury_token_a.owner == launch_config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = treasury_token_b.mint == mint_b.key() @ ErrorCode::InvalidTreasury,
+        constraint = treasury_token_b.owner == launch_config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"position_mint",
+            creator.key().as_ref()
+        ],
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub position_nft_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            POSITION_NFT_ACCOUNT_PREFIX,
+            position_nft_mint.key().as_ref()
+        ],
+        seeds::program = cp_amm::ID,
+        bump
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub position_nft_account: UncheckedAccount<'info>,
+    #[account(
+        address = POOL_AUTHORITY_ID
+    )]
+    /// CHECK: Account safely bound by address constraint
+    pub pool_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            CUSTOMIZABLE_POOL_PREFIX,
+            &max_key(&mint_a.key(), &mint_b.key()),
+            &min_key(&mint_a.key(), &mint_b.key()),
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            POSITION_PREFIX,
+            position_nft_mint.key().as_ref()
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub position: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            mint_a.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub token_a_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            mint_b.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub token_b_vault: UncheckedAccount<'info>,
+    // Creator's token accounts, the fees only pass through them
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::token_program = token_a_program,
+        associated_token::authority = creator,
+    )]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::token_program = token_b_program,
+        associated_token::authority = creator,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_a_program: Interface<'info, TokenInterface>,
+    pub token_b_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [b"__event_authority"],
+        seeds::program = cp_amm::ID,
+        bump
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub dammv2_event_authority: AccountInfo<'info>,
+    #[account(address = cp_amm::ID)]
+    /// CHECK: Self-CPI will fail if the program is not the current program
+    pub dammv2_program: AccountInfo<'info>,
+}
+
+impl<'info> ClaimCreatorPositionFees<'info> {
+    /// # Claim Creator Position Fees
+    ///
+    /// Claims the trading fees of the position `initialize_pool` opened for the creator, signed by the
+    /// creator PDA. With a `creator_fee_split`, each team wallet gets its share of both tokens, passed
+    /// as `[token_a_account, token_b_account]` pairs in the remaining accounts in the split order. The
+    /// treasury gets the rest.
+    pub fn claim_creator_position_fees(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<(Vec<TeamFeePayout>, CreatorPositionFeesClaimed)> {
+        // Always derived from the creator, so a caller can't leave the split out
+        let recipients = if self.creator_fee_split.owner == &crate::ID {
+            CreatorFeeSplit::try_deserialize(&mut &self.creator_fee_split.try_borrow_data()?[..])?.recipients
+        } else {
+            Vec::new()
+        };
+        require!(remaining_accounts.len() == recipients.len() * 2, ErrorCode::InvalidRemainingAccounts);
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"creator".as_ref(),
+            self.mint_b.to_account_info().key.as_ref(),
+            &self.creator.bump
+        ]];
+
+        let accounts = cp_amm::cpi::accounts::ClaimPositionFeeCtx {
+            pool_authority: self.pool_authority.to_account_info(),
+            pool: self.pool.to_account_info(),
+            position: self.position.to_account_info(),
+            token_a_account: self.token_a_account.to_account_info(),
+            token_b_account: self.token_b_account.to_account_info(),
+            token_a_vault: self.token_a_vault.to_account_info(),
+            token_b_vault: self.token_b_vault.to_account_info(),
+            token_a_mint: self.mint_a.to_account_info(),
+            token_b_mint: self.mint_b.to_account_info(),
+            position_nft_account: self.position_nft_account.to_account_info(),
+            owner: self.creator.to_account_info(),
+            token_a_program: self.token_a_program.to_account_info(),
+            token_b_program: self.token_b_program.to_account_info(),
+            event_authority: self.dammv2_event_authority.to_account_info(),
+            program: self.dammv2_program.to_account_info(),
+        };
+
+        let (amount_a, amount_b) = claim_position_fees(accounts, &signer_seeds)?;
+
+        let mut treasury_amount_a = amount_a;
+        let mut treasury_amount_b = amount_b;
+        let mut payouts = Vec::with_capacity(recipients.len());
+
+        for (i, recipient) in recipients.iter().enumerate() {
+            let (share_a, share_b) = (recipient.share(amount_a), recipient.share(amount_b));
+
+            // The transfers fail unless the token programs own the accounts
+            let team_token_a = TokenAccount::try_deserialize(&mut &remaining_accounts[i * 2].try_borrow_data()?[..])?;
+            let team_token_b = TokenAccount::try_deserialize(&mut &remaining_accounts[i * 2 + 1].try_borrow_data()?[..])?;
+
+            require!(
+                team_token_a.mint == self.mint_a.key() && team_token_a.owner == recipient.wallet,
+                ErrorCode::InvalidFeeSplit
+            );
+            require!(
+                team_token_b.mint == self.mint_b.key() && team_token_b.owner == recipient.wallet,
+                ErrorCode::InvalidFeeSplit
+            );
+
+            self.send_a(remaining_accounts[i * 2].clone(), share_a, &signer_seeds)?;
+            self.send_b(remaining_accounts[i * 2 + 1].clone(), share_b, &signer_seeds)?;
+
+            treasury_amount_a -= share_a;
+            treasury_amount_b -= share_b;
+
+            payouts.push(TeamFeePayout {
+                launch: self.creator.key(),
+                wallet: recipient.wallet,
+                amount_a: share_a,
+                amount_b: share_b,
+            });
+        }
+
+        self.send_a(self.treasury_token_a.to_account_info(), treasury_amount_a, &signer_seeds)?;
+        self.send_b(self.treasury_token_b.to_account_info(), treasury_amount_b, &signer_seeds)?;
+
+        Ok((payouts, CreatorPositionFeesClaimed {
+            launch: self.creator.key(),
+            amount_a,
+            amount_b,
+            treasury: self.launch_config.treasury,
+            treasury_amount_a,
+            treasury_amount_b,
+        }))
+    }
+
+    fn send_a(&self, to: AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_signed(
+            &self.token_a_program,
+            &self.mint_a,
+            self.token_a_account.to_account_info(),
+            to,
+            self.creator.to_account_info(),
+            signer_seeds,
+            amount,
+        )
+    }
+
+    fn send_b(&self, to: AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_signed(
+            &self.token_b_program,
+            &self.mint_b,
+            self.token_b_account.to_account_info(),
+            to,
+            self.creator.to_account_info(),
+            signer_seeds,
+            amount,
+        )
+    }
+}
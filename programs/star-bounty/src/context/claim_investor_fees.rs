@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::events::InvestorPayout;
+use crate::transfer_fee::SharePayout;
+use crate::state::{Creator, DistributionMode, DistributionPolicy, DistributionState, FeeIndex, FeeLedger, GlobalConfig, LaunchConfig, StreamCheckpoint, StreamLedger, StreamStatus};
+use crate::token::transfer_signed;
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimInvestorFees<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mint::token_program = token_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        constraint = distribution_policy.mode == DistributionMode::Pull @ ErrorCode::InvalidDistributionMode,
+        seeds = [
+            b"distribution_policy",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_index",
+            creator.key().as_ref(),
+        ],
+        bump = fee_index.bump[0],
+    )]
+    pub fee_index: Account<'info, FeeIndex>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_ledger",
+            creator.key().as_ref(),
+        ],
+        bump = fee_ledger.bump[0],
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+    /// CHECK: Checked against the creator stream PDA and deserialized in `claim_investor_fees`
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"stream_checkpoint",
+            metadata.key().as_ref(),
+        ],
+        bump = stream_checkpoint.bump[0],
+    )]
+    pub stream_checkpoint: Account<'info, StreamCheckpoint>,
+    #[account(
+        mut,
+        seeds = [
+            b"stream_ledger",
+            metadata.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: The stream ledger PDA, updated in `claim_investor_fees` once `initialize_stream_ledger` created it
+    pub stream_ledger: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub recipient_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            b"investor_fee_pos_owner",
+            mint_b.key().as_ref()
+        ],
+        bump = creator.position_owner_bump[0],
+    )]
+    /// CHECK: Data-less PDA, only used to sign for the investor position
+    pub investor_fee_pos_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::token_program = token_program,
+        associated_token::authority = investor_fee_pos_owner,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimInvestorFees<'info> {
+    /// # Claim Investor Fees
+    ///
+    /// Pays stream `index` the fees its checkpoint earned since its last claim, on the weight of its
+    /// previous refresh, then refreshes the weight to what the stream has locked now. Anyone can
+    /// call it, which also keeps the weights current, and the fees always go to the stream
+    /// `recipient_tokens`. Once Streamflow
+    /// closed the stream, they go to the `recipient_tokens` its checkpoint last read. The payout is
+    /// added to the stream ledger once it's initialized.
+    /// With a transfer fee on `mint_b`, the fee is sent on top out of `FeeIndex.transfer_fee_budget`
+    /// so the recipient receives the whole claim. Once the budget runs out, the recipient receives
+    /// the claim less the fee.
+    pub fn claim_investor_fees(&mut self, index: u32) -> Result<Option<InvestorPayout>> {
+        require_eq!(
+            self.creator.stream_metadata(&self.creator.key(), index),
+            self.metadata.key()
+        );
+
+        let status = self.launch_config.load_stream_status(&self.global_config, &self.metadata, &self.mint_b.key(), self.creator.legacy_stream(index))?;
+
+        // Follows the stream when its recipient is transferred
+        if let StreamStatus::Live(stream) = &status {
+            self.stream_checkpoint.recipient_tokens = stream.recipient_tokens;
+        }
+        require_keys_eq!(self.stream_checkpoint.recipient_tokens, self.recipient_tokens.key());
+
+        let now = Clock::get()?.unix_timestamp;
+
+        // Settles the fees earned on the previous weight and takes what the stream has locked now
+        self.stream_checkpoint.refresh(&mut self.fee_index, &status, now as u64)?;
+        let owed = self.stream_checkpoint.pending;
+        let mut event = None;
+
+        if owed > 0 {
+            let signer_seeds: [&[&[u8]]; 1] = [&[
+                b"investor_fee_pos_owner".as_ref(),
+                self.mint_b.to_account_info().key.as_ref(),
+                &self.creator.position_owner_bump
+            ]];
+
+            // The transfer fee comes out of the budget accruals kept back from the treasury part
+            let payout = SharePayout::new(&self.mint_b.to_account_info(), owed, self.fee_index.transfer_fee_budget)?;
+
+            transfer_signed(
+                &self.token_program,
+                &self.mint_b,
+                self.token_b_account.to_account_info(),
+                self.recipient_tokens.to_account_info(),
+                self.investor_fee_pos_owner.to_account_info(),
+                &signer_seeds,
+                payout.amount,
+            )?;
+
+            self.fee_index.transfer_fee_budget -= payout.fee_paid();
+            self.fee_ledger.record_payout(payout.received, 0, payout.withheld())?;
+
+            self.stream_checkpoint.pending = 0;
+            self.fee_index.unclaimed = self.fee_index.unclaimed
+                .checked_sub(owed)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            // Pull payouts carry the last accrual epoch
+            event = Some(InvestorPayout {
+                launch: self.creator.key(),
+                epoch: self.distribution_state.epoch,
+                stream_index: index,
+                recipient: self.recipient_tokens.owner,
+                locked: self.stream_checkpoint.weight,
+                share: payout.received,
+            });
+
+            StreamLedger::record(&self.stream_ledger, self.metadata.key, payout.received, now)?;
+        }
+
+        Ok(event)
+    }
+}
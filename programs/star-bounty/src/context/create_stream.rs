@@ -1,19 +1,92 @@
 use std::str::FromStr;
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke_signed;
-use anchor_spl::token::Mint;
-use anchor_spl::token::TokenAccount;
-use anchor_spl::token::Token;
+use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::token_interface::TokenInterface;
 use anchor_spl::associated_token::AssociatedToken;
-use streamflow_sdk::Create;
 
-use crate::state::Creator;
-use crate::ADMIN;
+use crate::events::StreamCreated;
+use crate::state::{Creator, DistributionState, GlobalConfig, LaunchConfig, StreamPermissions};
+use crate::error::ErrorCode;
 
+const START_TIME_TOLERANCE: i64 = 60 * 5; // 5 minutes
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateStreamArgs {
+    pub start_time: u64,
+    pub net_amount: u64,
+    /// Seconds between two releases
+    pub period: u64,
+    pub amount_per_period: u64,
+    /// Timestamp of the cliff, 0 for no cliff
+    pub cliff: u64,
+    /// Amount released at the cliff
+    pub cliff_amount: u64,
+    pub name: [u8; 64],
+    pub cancelable_by_sender: bool,
+    pub cancelable_by_recipient: bool,
+    pub transferable_by_sender: bool,
+    pub transferable_by_recipient: bool,
+}
+
+impl CreateStreamArgs {
+    /// Checks the schedule. Streams may start or hit their cliff later, distributions count
+    /// them as fully locked until then.
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(self.net_amount > 0 && self.amount_per_period > 0, ErrorCode::InvalidAmount);
+        require!(self.amount_per_period <= self.net_amount, ErrorCode::InvalidAmount);
+        require!(self.period > 0, ErrorCode::InvalidPeriod);
+        require!(self.cliff_amount <= self.net_amount, ErrorCode::InvalidCliff);
+        require!(self.cliff == 0 || self.cliff >= self.start_time, ErrorCode::InvalidCliff);
+        require!(
+            self.start_time as i64 >= now.saturating_sub(START_TIME_TOLERANCE),
+            ErrorCode::InvalidStartTime
+        );
+
+        Ok(())
+    }
+
+    pub fn permissions(&self) -> StreamPermissions {
+        StreamPermissions {
+            cancelable_by_sender: self.cancelable_by_sender,
+            cancelable_by_recipient: self.cancelable_by_recipient,
+            transferable_by_sender: self.transferable_by_sender,
+            transferable_by_recipient: self.transferable_by_recipient,
+        }
+    }
+
+    /// Creates the Streamflow stream described by these args.
+    ///
+    /// Top-ups stay disabled: `claim_fees` reads the deposited amount as the initial locked amount
+    pub fn create<'info>(&self, ctx: CpiContext<'_, '_, '_, 'info, streamflow_sdk::cpi::accounts::Create<'info>>) -> Result<()> {
+        streamflow_sdk::cpi::create(
+            ctx, 
+            self.start_time, 
+            self.net_amount,
+            self.period,
+            self.amount_per_period,
+            self.cliff,
+            self.cliff_amount,
+            self.cancelable_by_sender,
+            self.cancelable_by_recipient,
+            true,
+            self.transferable_by_sender,
+            self.transferable_by_recipient,
+            false,
+            self.name,
+            self.period,
+            None,
+            None,
+        )
+    }
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateStream<'info> {
-    pub mint: Account<'info, Mint>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
         seeds = [
@@ -23,15 +96,39 @@ pub struct CreateStream<'info> {
         bump = creator.bump[0]
     )]
     pub creator: Account<'info, Creator>,
-
-    #[account(mut, address = ADMIN)]
+    #[account(
+        mut,
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, address = global_config.stream_manager @ ErrorCode::Unauthorized)]
     pub sender: Signer<'info>,
     #[account(
         mut,
         associated_token::mint = mint,
+        associated_token::token_program = token_program,
         associated_token::authority = sender,
     )]
-    pub sender_tokens: Box<Account<'info, TokenAccount>>,
+    pub sender_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
     /// CHECK: The admin pass this account so it's fine
     #[account(mut)]
     pub recipient: UncheckedAccount<'info>,
@@ -40,7 +137,7 @@ pub struct CreateStream<'info> {
         seeds = [
             b"metadata",
             creator.key().as_ref(),
-            &[creator.streams],
+            &creator.streams.to_le_bytes(),
         ],
         bump,
     )]
@@ -60,18 +157,20 @@ pub struct CreateStream<'info> {
     #[account(
         mut,
         associated_token::mint = mint,
+        associated_token::token_program = token_program,
         associated_token::authority = recipient,
     )]
-    pub recipient_tokens: Box<Account<'info, TokenAccount>>,
+    pub recipient_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(mut, address = Pubkey::from_str(streamflow_sdk::state::STRM_TREASURY).unwrap())]
     /// CHECK: Checked by address constraint
     pub streamflow_treasury: UncheckedAccount<'info>,
     #[account(
         mut,
         associated_token::mint = mint,
+        associated_token::token_program = token_program,
         associated_token::authority = streamflow_treasury,
     )]
-    pub streamflow_treasury_tokens: Box<Account<'info, TokenAccount>>,
+    pub streamflow_treasury_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(mut, address = Pubkey::from_str(streamflow_sdk::state::WITHDRAWOR_ADDRESS).unwrap())]
     /// CHECK: Checked by address constraint
     pub withdrawor: UncheckedAccount<'info>,
@@ -80,17 +179,21 @@ pub struct CreateStream<'info> {
     #[account(address = streamflow_sdk::id())]
     /// CHECK: Checked by address constraint
     pub timelock_program: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateStream<'info> {
-    pub fn create_stream(&mut self, bump: [u8; 1]) -> Result<()> {
+    pub fn create_stream(&mut self, args: CreateStreamArgs, bump: [u8; 1]) -> Result<StreamCreated> {
+        args.validate(Clock::get()?.unix_timestamp)?;
+        self.launch_config.register_stream(&args.permissions(), args.net_amount)?;
+
+        let index = self.creator.streams.to_le_bytes();
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"metadata",
             self.creator.to_account_info().key.as_ref(),
-            &[self.creator.streams],
+            &index,
             &bump,
         ]];
 
@@ -119,29 +222,21 @@ impl<'info> CreateStream<'info> {
             signer_seeds
         );
 
-        streamflow_sdk::cpi::create(
-            ctx, 
-            Clock::get()?.unix_timestamp as u64, 
-            1_000_000,
-            60 * 60 * 24 * 30,
-            1_000_000,
-            0,
-            0,
-            true,
-            false,
-            true,
-            true,
-            false,
-            true,
-            [0; 64],
-            60 * 60 * 24 * 30,
-            None,
-            None,
-        )?;
+        args.create(ctx)?;
+
+        let event = StreamCreated {
+            launch: self.creator.key(),
+            epoch: self.distribution_state.epoch,
+            stream_index: self.creator.streams,
+            metadata: self.metadata.key(),
+            recipient: self.recipient.key(),
+            net_amount: args.net_amount,
+            start_time: args.start_time,
+        };
 
         // Add a Stream
-        self.creator.streams += 1;
+        self.creator.streams = self.creator.streams.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
 
-        Ok(())
+        Ok(event)
     }
 }
\ No newline at end of file
@@ -0,0 +1,182 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::token_interface::TokenInterface;
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+
+use crate::context::CreateStreamArgs;
+use crate::events::StreamCreated;
+use crate::state::{Creator, DistributionState, GlobalConfig, LaunchConfig};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateStreamsBatch<'info> {
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0]
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        mut,
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, address = global_config.stream_manager @ ErrorCode::Unauthorized)]
+    pub sender: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+        associated_token::authority = sender,
+    )]
+    pub sender_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = Pubkey::from_str(streamflow_sdk::state::STRM_TREASURY).unwrap())]
+    /// CHECK: Checked by address constraint
+    pub streamflow_treasury: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program,
+        associated_token::authority = streamflow_treasury,
+    )]
+    pub streamflow_treasury_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = Pubkey::from_str(streamflow_sdk::state::WITHDRAWOR_ADDRESS).unwrap())]
+    /// CHECK: Checked by address constraint
+    pub withdrawor: UncheckedAccount<'info>,
+    /// The Rent Sysvar account.
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = streamflow_sdk::id())]
+    /// CHECK: Checked by address constraint
+    pub timelock_program: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStreamsBatch<'info> {
+    /// # Create Streams Batch
+    ///
+    /// Creates one stream per allocation, consuming `[recipient, recipient_tokens, metadata, escrow_tokens]`
+    /// from the remaining accounts for each of them. Metadata PDAs are derived from consecutive indexes
+    /// starting at `creator.streams`, which is only advanced once every stream is created.
+    pub fn create_streams_batch(&mut self, allocations: Vec<CreateStreamArgs>, remaining_accounts: &[AccountInfo<'info>]) -> Result<Vec<StreamCreated>> {
+        require!(!allocations.is_empty(), ErrorCode::InvalidAmount);
+        require_eq!(remaining_accounts.len(), allocations.len() * 4);
+
+        let now = Clock::get()?.unix_timestamp;
+        let creator_key = self.creator.key();
+        let mut events = Vec::with_capacity(allocations.len());
+
+        for (i, args) in allocations.iter().enumerate() {
+            args.validate(now)?;
+            self.launch_config.register_stream(&args.permissions(), args.net_amount)?;
+
+            let recipient = &remaining_accounts[i * 4];
+            let recipient_tokens = &remaining_accounts[i * 4 + 1];
+            let metadata = &remaining_accounts[i * 4 + 2];
+            let escrow_tokens = &remaining_accounts[i * 4 + 3];
+
+            let stream_index = self.creator.streams
+                .checked_add(i as u32)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            let index = stream_index.to_le_bytes();
+
+            let (expected_metadata, bump) = Pubkey::find_program_address(&[
+                b"metadata",
+                creator_key.as_ref(),
+                &index,
+            ], &crate::ID);
+            require_keys_eq!(expected_metadata, metadata.key());
+
+            let (expected_escrow, _) = Pubkey::find_program_address(&[
+                b"strm",
+                metadata.key.as_ref(),
+            ], &streamflow_sdk::id());
+            require_keys_eq!(expected_escrow, escrow_tokens.key());
+
+            require_keys_eq!(
+                get_associated_token_address_with_program_id(recipient.key, &self.mint.key(), self.token_program.key),
+                recipient_tokens.key()
+            );
+
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"metadata",
+                creator_key.as_ref(),
+                &index,
+                &[bump],
+            ]];
+
+            let ctx = CpiContext::new_with_signer(
+                self.timelock_program.to_account_info(),
+                streamflow_sdk::cpi::accounts::Create {
+                    sender: self.sender.to_account_info(),
+                    sender_tokens: self.sender_tokens.to_account_info(),
+                    recipient: recipient.to_account_info(),
+                    metadata: metadata.to_account_info(),
+                    escrow_tokens: escrow_tokens.to_account_info(),
+                    recipient_tokens: recipient_tokens.to_account_info(),
+                    streamflow_treasury: self.streamflow_treasury.to_account_info(),
+                    streamflow_treasury_tokens: self.streamflow_treasury_tokens.to_account_info(),
+                    withdrawor: self.withdrawor.to_account_info(),
+                    partner: self.streamflow_treasury.to_account_info(),
+                    partner_tokens: self.streamflow_treasury_tokens.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    fee_oracle: self.streamflow_treasury.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                    timelock_program: self.timelock_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    associated_token_program: self.associated_token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                },
+                signer_seeds
+            );
+
+            args.create(ctx)?;
+
+            events.push(StreamCreated {
+                launch: creator_key,
+                epoch: self.distribution_state.epoch,
+                stream_index,
+                metadata: metadata.key(),
+                recipient: recipient.key(),
+                net_amount: args.net_amount,
+                start_time: args.start_time,
+            });
+        }
+
+        // Add all the Streams
+        self.creator.streams = self.creator.streams
+            .checked_add(allocations.len() as u32)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(events)
+    }
+}
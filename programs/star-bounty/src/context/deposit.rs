@@ -1,30 +1,63 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{Mint, Token, TokenAccount}, token_2022::Token2022
+    token_interface::{Mint, TokenAccount, TokenInterface}, token_2022::Token2022
 };
 use cp_amm::{
     const_pda::pool_authority::ID as POOL_AUTHORITY_ID, constants::seeds::{CUSTOMIZABLE_POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX, TOKEN_VAULT_PREFIX }, instructions::initialize_pool::{max_key, min_key}, safe_math::SafeMath, state::{CollectFeeMode, Pool}
 };
 use ruint::aliases::U256;
-use crate::{state::InvestorFeePositionOwnerPda, ADMIN};
+use crate::events::InvestorPositionDeposited;
+use crate::state::{Creator, DistributionState, GlobalConfig, LaunchConfig};
 use crate::error::ErrorCode;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
-        init,
-        payer = payer,
-        space = InvestorFeePositionOwnerPda::DISCRIMINATOR.len() + InvestorFeePositionOwnerPda::INIT_SPACE,
         seeds = [
             b"investor_fee_pos_owner", 
             mint_b.key().as_ref()
         ],
         bump,
     )]
-    pub investor_fee_pos_owner: Account<'info, InvestorFeePositionOwnerPda>,
+    /// CHECK: Data-less PDA, only used to sign for the investor position
+    pub investor_fee_pos_owner: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
 
     // DAMM V2 Accounts
-    #[account(mut, address = ADMIN)]
+    #[account(mut, address = global_config.position_manager @ ErrorCode::Unauthorized)]
     pub payer: Signer<'info>,
     #[account(
         mut,
@@ -39,7 +72,7 @@ pub struct Deposit<'info> {
     #[account(
         mut,
         seeds = [
-            POSITION_NFT_ACCOUNT_PREFIX.as_ref(), 
+            POSITION_NFT_ACCOUNT_PREFIX, 
             position_nft_mint.key().as_ref()
         ],
         seeds::program = cp_amm::ID,
@@ -55,7 +88,7 @@ pub struct Deposit<'info> {
     #[account(
         mut,
         seeds = [
-            CUSTOMIZABLE_POOL_PREFIX.as_ref(),
+            CUSTOMIZABLE_POOL_PREFIX,
             &max_key(&mint_a.key(), &mint_b.key()),
             &min_key(&mint_a.key(), &mint_b.key()),
         ],
@@ -67,7 +100,7 @@ pub struct Deposit<'info> {
     #[account(
         mut,
         seeds = [
-            POSITION_PREFIX.as_ref(),
+            POSITION_PREFIX,
             position_nft_mint.key().as_ref()
         ],
         seeds::program = cp_amm::ID,
@@ -75,12 +108,14 @@ pub struct Deposit<'info> {
     )]
     /// CHECK: Account checked and initialized by DAMMV2
     pub position: UncheckedAccount<'info>,
-    pub mint_a: Box<Account<'info, Mint>>,
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_a_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_b_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
         seeds = [
-            TOKEN_VAULT_PREFIX.as_ref(),
+            TOKEN_VAULT_PREFIX,
             mint_a.key().as_ref(),
             pool.key().as_ref(),
         ],
@@ -92,7 +127,7 @@ pub struct Deposit<'info> {
     #[account(
         mut,
         seeds = [
-            TOKEN_VAULT_PREFIX.as_ref(),
+            TOKEN_VAULT_PREFIX,
             mint_b.key().as_ref(),
             pool.key().as_ref(),
         ],
@@ -105,16 +140,19 @@ pub struct Deposit<'info> {
     #[account(
         mut,
         associated_token::mint = mint_a,
+        associated_token::token_program = token_a_program,
         associated_token::authority = investor_fee_pos_owner,
     )]
-    payer_token_a: Box<Account<'info, TokenAccount>>,
+    payer_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = mint_b,
+        associated_token::token_program = token_b_program,
         associated_token::authority = investor_fee_pos_owner,
     )]
-    payer_token_b: Box<Account<'info, TokenAccount>>,
-    token_program: Program<'info, Token>,
+    payer_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_a_program: Interface<'info, TokenInterface>,
+    token_b_program: Interface<'info, TokenInterface>,
     token_2022_program: Program<'info, Token2022>,
     system_program: Program<'info, System>,
     #[account(
@@ -123,7 +161,7 @@ pub struct Deposit<'info> {
         bump
     )]
     /// CHECK: Account checked and initialized by DAMMV2
-    pub event_authority: AccountInfo<'info>,
+    pub dammv2_event_authority: AccountInfo<'info>,
     #[account(address = cp_amm::ID)]
     /// CHECK: Self-CPI will fail if the program is not the current program
     pub dammv2_program: AccountInfo<'info>,
@@ -132,16 +170,10 @@ pub struct Deposit<'info> {
 impl<'info> Deposit<'info> {
     /// # Initialize Investor Fee Position Owner
     /// 
-    /// This account will own the position in the pool and will be used to collect fees 
-    /// and check for cranking every 24h.
+    /// The `investor_fee_pos_owner` PDA owns the position in the pool and is used to collect fees,
+    /// its bump is stored in the `Creator` that tracks cranking every 24h.
     pub fn initialize_investor_fee_pos_owner(&mut self, bump: [u8; 1]) -> Result<()> {
-        self.investor_fee_pos_owner.set_inner(
-            InvestorFeePositionOwnerPda {
-                associated_mint: self.mint_b.key(),
-                last_claimed_at: 0,
-                bump,
-            }
-        );
+        self.creator.position_owner_bump = bump;
 
         Ok(())
     }
@@ -149,7 +181,7 @@ impl<'info> Deposit<'info> {
     /// # Deposit
     /// 
     /// This function will deposit the tokens into the pool and create the position.
-    pub fn deposit(&mut self, position_nft_mint_bump: [u8; 1]) -> Result<()> {
+    pub fn deposit(&mut self, position_nft_mint_bump: [u8; 1]) -> Result<InvestorPositionDeposited> {
         // Deserialize and check that the pool accept only fee in TokenB (our token)
         let pool = Pool::try_deserialize(&mut &self.pool.try_borrow_mut_data()?[..])?;
         if pool.collect_fee_mode != CollectFeeMode::OnlyB as u8 {
@@ -167,7 +199,7 @@ impl<'info> Deposit<'info> {
             payer: self.payer.to_account_info(),
             token_program: self.token_2022_program.to_account_info(),
             system_program: self.system_program.to_account_info(),
-            event_authority: self.event_authority.to_account_info(),
+            event_authority: self.dammv2_event_authority.to_account_info(),
             program: self.dammv2_program.to_account_info(),
         };
 
@@ -189,7 +221,7 @@ impl<'info> Deposit<'info> {
         let signer_seeds: [&[&[u8]];1] = [&[
             b"investor_fee_pos_owner".as_ref(),
             self.mint_b.to_account_info().key.as_ref(),
-            &self.investor_fee_pos_owner.bump
+            &self.creator.position_owner_bump
         ]];
 
         let accounts = cp_amm::cpi::accounts::AddLiquidityCtx {
@@ -203,9 +235,9 @@ impl<'info> Deposit<'info> {
             token_a_mint: self.mint_a.to_account_info(),
             token_b_mint: self.mint_b.to_account_info(),
             position_nft_account: self.position_nft_account.to_account_info(),
-            token_a_program: self.token_program.to_account_info(),
-            token_b_program: self.token_program.to_account_info(),
-            event_authority: self.event_authority.to_account_info(),
+            token_a_program: self.token_a_program.to_account_info(),
+            token_b_program: self.token_b_program.to_account_info(),
+            event_authority: self.dammv2_event_authority.to_account_info(),
             program: self.dammv2_program.to_account_info(),
         };
 
@@ -229,7 +261,18 @@ impl<'info> Deposit<'info> {
             token_b_amount_threshold: self.payer_token_b.amount,
         })?;
 
-        Ok(())
+        let (balance_a, balance_b) = (self.payer_token_a.amount, self.payer_token_b.amount);
+        self.payer_token_a.reload()?;
+        self.payer_token_b.reload()?;
+
+        Ok(InvestorPositionDeposited {
+            launch: self.creator.key(),
+            epoch: self.distribution_state.epoch,
+            position: self.position.key(),
+            liquidity: liquidity_delta,
+            token_a_amount: balance_a - self.payer_token_a.amount,
+            token_b_amount: balance_b - self.payer_token_b.amount,
+        })
     }
 
     /// # Calculate Liquidity
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::events::InvestorPayout;
+use crate::transfer_fee::SharePayout;
+use crate::state::{locked_amount, Creator, DistributionPhase, DistributionPolicy, DistributionState, FeeLedger, GlobalConfig, LaunchConfig, StreamLedgers, StreamStatus};
+use crate::token::transfer_signed;
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DistributePage<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        constraint = global_config.cranker.map_or(true, |cranker| cranker == signer.key()) @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mint::token_program = token_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        seeds = [
+            b"distribution_policy",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        mut,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_ledger",
+            creator.key().as_ref(),
+        ],
+        bump = fee_ledger.bump[0],
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+    #[account(
+        seeds = [
+            b"investor_fee_pos_owner",
+            mint_b.key().as_ref()
+        ],
+        bump = creator.position_owner_bump[0],
+    )]
+    /// CHECK: Data-less PDA, only used to sign for the investor position
+    pub investor_fee_pos_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::token_program = token_program,
+        associated_token::authority = investor_fee_pos_owner,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DistributePage<'info> {
+    /// # Distribute Page
+    ///
+    /// Processes streams `[start_index, start_index + count)`, passed in the remaining accounts as
+    /// their `metadata` accounts while tallying, and as `[metadata, recipient_tokens]` pairs followed
+    /// by the stream ledgers to update, if any, while paying. While tallying, the page adds its locked amounts to the epoch
+    /// total, removes the unvested remainder of its cancelled streams from Y0, and the last tallied page fixes the investor amount. While paying, the page sends each
+    /// stream its pro-rata share. Both passes compute the locked amounts at `started_at` from the
+    /// vesting schedule, withdrawals aside, so they agree unless a stream is cancelled, closed or
+    /// paused in between. Such a stream gets less or nothing, the shares are capped to what's left
+    /// of the investor amount, and the unpaid part is handled by `finalize_distribution`.
+    pub fn distribute_page(&mut self, start_index: u32, count: u32, remaining_accounts: &[AccountInfo<'info>]) -> Result<Vec<InvestorPayout>> {
+        match self.distribution_state.phase {
+            DistributionPhase::Idle => err!(ErrorCode::NoDistributionInProgress),
+            DistributionPhase::Tallying => {
+                // Only the locked amounts are read, `metadata` of every stream
+                require!(remaining_accounts.len() == count as usize, ErrorCode::InvalidRemainingAccounts);
+                self.tally_page(start_index, count, remaining_accounts)?;
+                Ok(Vec::new())
+            }
+            DistributionPhase::Paying => {
+                // `[metadata, recipient_tokens]` pairs followed by the stream ledgers to update, if any
+                require!(
+                    remaining_accounts.len() >= count as usize * 2 && remaining_accounts.len() <= count as usize * 3,
+                    ErrorCode::InvalidRemainingAccounts
+                );
+                self.pay_page(start_index, count, remaining_accounts)
+            }
+        }
+    }
+
+    fn tally_page(&mut self, start_index: u32, count: u32, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let mut bitmap = self.distribution_state.tallied_pages;
+        self.distribution_state.mark_pages(&mut bitmap, start_index, count)?;
+        self.distribution_state.tallied_pages = bitmap;
+
+        let mut initial_locked = self.distribution_state.initial_locked;
+        let mut total_locked = self.distribution_state.total_locked;
+
+        for i in 0..count {
+            let stream = match self.load_stream(start_index + i, &remaining_accounts[i as usize])? {
+                StreamStatus::Live(stream) => stream,
+                StreamStatus::Ended => continue,
+                StreamStatus::Cancelled { unvested } => {
+                    initial_locked = initial_locked.saturating_sub(unvested);
+                    continue;
+                }
+            };
+
+            total_locked = total_locked
+                .checked_add(locked_amount(&stream, self.distribution_state.started_at as u64))
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        let state = &mut self.distribution_state;
+        state.initial_locked = initial_locked;
+        state.total_locked = total_locked;
+
+        // Every stream is counted, the investor amount can be fixed
+        if state.all_pages_done(&state.tallied_pages) {
+            let quote = self.distribution_policy.investor_quote(state.claimed, total_locked, initial_locked)?;
+            state.owed = self.distribution_policy.investor_owed(quote, state.investor_reserve)?;
+            state.distributable = self.distribution_policy.investor_distributable(state.owed);
+            state.phase = DistributionPhase::Paying;
+        }
+
+        Ok(())
+    }
+
+    fn pay_page(&mut self, start_index: u32, count: u32, remaining_accounts: &[AccountInfo<'info>]) -> Result<Vec<InvestorPayout>> {
+        let mut bitmap = self.distribution_state.paid_pages;
+        self.distribution_state.mark_pages(&mut bitmap, start_index, count)?;
+        self.distribution_state.paid_pages = bitmap;
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"investor_fee_pos_owner".as_ref(),
+            self.mint_b.to_account_info().key.as_ref(),
+            &self.creator.position_owner_bump
+        ]];
+
+        let now = Clock::get()?.unix_timestamp;
+        let total_locked = self.distribution_state.total_locked;
+        let distributable = self.distribution_state.distributable;
+        let mut settled = self.distribution_state.settled;
+        let paid_before = self.distribution_state.distributed;
+        let mut distributed = paid_before;
+        let withheld_before = self.distribution_state.transfer_fees;
+        let mut transfer_fees = withheld_before;
+
+        // Transfer fees on the payouts come out of the treasury part, as far as it goes. The previous
+        // pages paid what they sent beyond the shares out of it.
+        let fees_paid = transfer_fees
+            .checked_add(distributed)
+            .and_then(|x| x.checked_sub(settled))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let mut fee_budget = self.distribution_state.claimed
+            .checked_add(self.distribution_state.investor_reserve)
+            .and_then(|x| x.checked_sub(self.distribution_state.owed))
+            .and_then(|x| x.checked_sub(fees_paid))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut received = vec![0u64; count as usize];
+        let mut payouts = Vec::new();
+
+        for i in 0..count {
+            let StreamStatus::Live(stream) = self.load_stream(start_index + i, &remaining_accounts[i as usize * 2])? else {
+                continue;
+            };
+            require_eq!(stream.recipient_tokens, remaining_accounts[i as usize * 2 + 1].key());
+
+            let locked = locked_amount(&stream, self.distribution_state.started_at as u64);
+            if locked == 0 {
+                continue;
+            }
+
+            // weight_i = locked_i / total_locked, never above what's left of the investor amount
+            let share = (distributable as u128)
+                .checked_mul(locked as u128)
+                .and_then(|x| x.checked_div(total_locked as u128))
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            let share = std::cmp::min(u64::try_from(share)?, distributable - settled);
+
+            if share == 0 {
+                continue;
+            }
+
+            // Send the mint's transfer fee on top so the stream receives exactly its share
+            let payout = SharePayout::new(&self.mint_b.to_account_info(), share, fee_budget)?;
+            fee_budget -= payout.fee_paid();
+
+            transfer_signed(
+                &self.token_program,
+                &self.mint_b,
+                self.token_b_account.to_account_info(),
+                remaining_accounts[i as usize * 2 + 1].to_account_info(),
+                self.investor_fee_pos_owner.to_account_info(),
+                &signer_seeds,
+                payout.amount,
+            )?;
+
+            payouts.push(InvestorPayout {
+                launch: self.creator.key(),
+                epoch: self.distribution_state.epoch,
+                stream_index: start_index + i,
+                recipient: stream.recipient,
+                locked,
+                share: payout.received,
+            });
+
+            received[i as usize] = payout.received;
+            settled = settled.checked_add(share).ok_or(ProgramError::ArithmeticOverflow)?;
+            distributed = distributed.checked_add(payout.received).ok_or(ProgramError::ArithmeticOverflow)?;
+            transfer_fees = transfer_fees.checked_add(payout.withheld()).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        let mut ledgers = StreamLedgers::new(&remaining_accounts[count as usize * 2..]);
+        for (i, &amount) in received.iter().enumerate() {
+            ledgers.record(remaining_accounts[i * 2].key, amount, now)?;
+        }
+        ledgers.finish()?;
+
+        self.distribution_state.settled = settled;
+        self.distribution_state.distributed = distributed;
+        self.fee_ledger.record_payout(distributed - paid_before, 0, transfer_fees - withheld_before)?;
+        self.distribution_state.transfer_fees = transfer_fees;
+
+        Ok(payouts)
+    }
+
+    /// Checks the `metadata` account of stream `index` and loads it.
+    fn load_stream(&self, index: u32, metadata_account: &AccountInfo<'info>) -> Result<StreamStatus> {
+        require_eq!(
+            self.creator.stream_metadata(&self.creator.key(), index),
+            metadata_account.key()
+        );
+
+        self.launch_config.load_stream_status(&self.global_config, metadata_account, &self.mint_b.key(), self.creator.legacy_stream(index))
+    }
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::events::{CreatorRemainder, DistributionEvents, FeesDistributed};
+use crate::state::{Creator, DistributionPhase, DistributionPolicy, DistributionState, FeeLedger, GlobalConfig, LaunchConfig};
+use crate::token::transfer_signed;
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeDistribution<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        constraint = global_config.cranker.map_or(true, |cranker| cranker == signer.key()) @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mint::token_program = token_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        seeds = [
+            b"distribution_policy",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        mut,
+        constraint = distribution_state.phase == DistributionPhase::Paying @ ErrorCode::NoDistributionInProgress,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_ledger",
+            creator.key().as_ref(),
+        ],
+        bump = fee_ledger.bump[0],
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == mint_b.key() @ ErrorCode::InvalidTreasury,
+        constraint = creator_token_account.owner == launch_config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            b"investor_fee_pos_owner",
+            mint_b.key().as_ref()
+        ],
+        bump = creator.position_owner_bump[0],
+    )]
+    /// CHECK: Data-less PDA, only used to sign for the investor position
+    pub investor_fee_pos_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::token_program = token_program,
+        associated_token::authority = investor_fee_pos_owner,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FinalizeDistribution<'info> {
+    /// # Finalize Distribution
+    ///
+    /// Closes the epoch once every page is paid. What's still owed to investors stays in the
+    /// reserve with carry-over, the rest of the claimed fees and reserve goes to the treasury.
+    pub fn finalize_distribution(&mut self) -> Result<DistributionEvents> {
+        let state = &self.distribution_state;
+        require!(state.all_pages_done(&state.paid_pages), ErrorCode::PagesPending);
+
+        let investor_reserve = self.distribution_policy.investor_reserve(state.owed, state.settled, state.investor_reserve, state.total_locked);
+        let remainder = state.claimed
+            .checked_add(state.investor_reserve)
+            .and_then(|x| x.checked_sub(state.distributed))
+            .and_then(|x| x.checked_sub(investor_reserve))
+            .and_then(|x| x.checked_sub(state.transfer_fees))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut events = DistributionEvents::default();
+
+        if remainder > 0 {
+            let signer_seeds: [&[&[u8]]; 1] = [&[
+                b"investor_fee_pos_owner".as_ref(),
+                self.mint_b.to_account_info().key.as_ref(),
+                &self.creator.position_owner_bump
+            ]];
+
+            transfer_signed(
+                &self.token_program,
+                &self.mint_b,
+                self.token_b_account.to_account_info(),
+                self.creator_token_account.to_account_info(),
+                self.investor_fee_pos_owner.to_account_info(),
+                &signer_seeds,
+                remainder,
+            )?;
+
+            events.creator_remainder = Some(CreatorRemainder {
+                launch: self.creator.key(),
+                epoch: self.distribution_state.epoch,
+                treasury: self.creator_token_account.key(),
+                amount: remainder,
+            });
+        }
+
+        self.fee_ledger.record_payout(0, remainder, 0)?;
+
+        events.distributed = Some(FeesDistributed {
+            launch: self.creator.key(),
+            epoch: self.distribution_state.epoch,
+            fees: self.distribution_state.claimed,
+            distributed: self.distribution_state.distributed,
+            treasury_amount: remainder,
+            investor_reserve,
+        });
+
+        self.distribution_state.investor_reserve = investor_reserve;
+        self.distribution_state.phase = DistributionPhase::Idle;
+
+        Ok(events)
+    }
+}
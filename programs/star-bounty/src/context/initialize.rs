@@ -1,24 +1,251 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{revoke, Revoke, approve, Approve, Token, Mint, TokenAccount}, 
+    token_interface::{revoke, Revoke, approve, Approve, TokenInterface, Mint, TokenAccount}, 
     token_2022::Token2022, 
 };
 
 use cp_amm::{
-    const_pda::pool_authority::ID as POOL_AUTHORITY_ID, constants::{seeds::{CUSTOMIZABLE_POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX, TOKEN_VAULT_PREFIX }, MAX_SQRT_PRICE, MIN_SQRT_PRICE}, cpi::accounts::InitializeCustomizablePoolCtx, instructions::initialize_pool::{max_key, min_key}, params::fee_parameters::{BaseFeeParameters, PoolFeeParameters}, safe_math::SafeMath, state::CollectFeeMode, InitializeCustomizablePoolParameters,
+    const_pda::pool_authority::ID as POOL_AUTHORITY_ID, constants::{fee::{FEE_DENOMINATOR, MAX_FEE_NUMERATOR, MIN_FEE_NUMERATOR}, seeds::{CUSTOMIZABLE_POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX, TOKEN_VAULT_PREFIX }, BASIS_POINT_MAX, BIN_STEP_BPS_DEFAULT, BIN_STEP_BPS_U128_DEFAULT, MAX_SQRT_PRICE, MIN_SQRT_PRICE}, cpi::accounts::InitializeCustomizablePoolCtx, instructions::initialize_pool::{max_key, min_key}, params::fee_parameters::{BaseFeeParameters, DynamicFeeParameters, PoolFeeParameters}, safe_math::SafeMath, state::CollectFeeMode, InitializeCustomizablePoolParameters,
     utils_math::sqrt_u256,
 };
 use ruint::aliases::U256;
-use crate::state::Creator;
+use crate::state::{ActivationType, Creator, DistributionPolicy, DistributionState, FeeIndex, FeeLedger, GlobalConfig, LaunchConfig, StreamPermissions};
+use crate::events::PoolInitialized;
 use crate::error::ErrorCode;
 
-const COMMITMENT_IN_BPS: u16 = 1_000; // 10%
-const POOL_AMOUNT: u64 = 1_000_000_000_000; // 10% of a 10M token supply (with 6 decimals)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LaunchConfigArgs {
+    /// Share of the token A balance committed to the pool, in bps
+    pub commitment_bps: u16,
+    /// Amount of token B deposited in the pool
+    pub pool_token_amount: u64,
+    /// Decimals of token B, pinned to catch mismatched launch parameters
+    pub decimals: u8,
+    /// Cancel/transfer rights the investor streams are allowed to grant
+    pub stream_permissions: StreamPermissions,
+    /// Owner of the token B account receiving the creator remainder of each claim
+    pub treasury: Pubkey,
+    /// Price range of the creator position
+    pub price_range: PriceRange,
+    /// Fee schedule of the pool
+    pub pool_fees: PoolFeeArgs,
+    /// Unit of `activation_point` and of the fee scheduler periods
+    pub activation_type: ActivationType,
+    /// Slot or timestamp trading opens at, the pool trades as soon as it's created without one
+    pub activation_point: Option<u64>,
+    /// Reserves the pre-activation window for an alpha vault of whitelisted buyers
+    pub has_alpha_vault: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PoolFeeArgs {
+    /// Base fee at activation, over `FEE_DENOMINATOR` (1e9)
+    pub cliff_fee_numerator: u64,
+    /// Decays the base fee after activation, a flat base fee without it
+    pub fee_scheduler: Option<FeeSchedulerArgs>,
+    /// Adds a volatility fee on top of the base fee
+    pub dynamic_fee: Option<DynamicFeeArgs>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FeeSchedulerMode {
+    /// The fee drops by `reduction_factor` numerator each period
+    Linear,
+    /// The fee drops by `reduction_factor` bps of the current fee each period
+    Exponential,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeSchedulerArgs {
+    pub mode: FeeSchedulerMode,
+    pub number_of_period: u16,
+    /// Period length in the pool activation unit, slots or seconds
+    pub period_frequency: u64,
+    pub reduction_factor: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DynamicFeeArgs {
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub max_volatility_accumulator: u32,
+    pub variable_fee_control: u32,
+}
+
+impl PoolFeeArgs {
+    /// Maps the arguments onto the DAMM v2 fee parameters, checked against its limits:
+    /// the base fee has to stay within `MIN_FEE_NUMERATOR` and `MAX_FEE_NUMERATOR` over the
+    /// whole schedule, and the dynamic fee uses the default bin step.
+    pub fn pool_fee_parameters(&self) -> Result<PoolFeeParameters> {
+        require!(
+            self.cliff_fee_numerator >= MIN_FEE_NUMERATOR
+                && self.cliff_fee_numerator <= MAX_FEE_NUMERATOR
+                && self.cliff_fee_numerator < FEE_DENOMINATOR,
+            ErrorCode::InvalidPoolFees
+        );
+
+        let base_fee = match self.fee_scheduler {
+            None => BaseFeeParameters {
+                cliff_fee_numerator: self.cliff_fee_numerator,
+                first_factor: 0,
+                second_factor: [0u8; 8],
+                third_factor: 0,
+                base_fee_mode: 0,
+            },
+            Some(scheduler) => {
+                require!(
+                    scheduler.number_of_period > 0 && scheduler.period_frequency > 0 && scheduler.reduction_factor > 0,
+                    ErrorCode::InvalidPoolFees
+                );
+
+                let min_fee_numerator = match scheduler.mode {
+                    FeeSchedulerMode::Linear => self.cliff_fee_numerator
+                        .checked_sub(scheduler.reduction_factor.saturating_mul(scheduler.number_of_period as u64)),
+                    FeeSchedulerMode::Exponential => {
+                        require!(scheduler.reduction_factor < BASIS_POINT_MAX, ErrorCode::InvalidPoolFees);
+                        Some(Self::exponential_fee(self.cliff_fee_numerator, scheduler.reduction_factor, scheduler.number_of_period)?)
+                    }
+                };
+                require!(
+                    min_fee_numerator.is_some_and(|fee| fee >= MIN_FEE_NUMERATOR),
+                    ErrorCode::InvalidPoolFees
+                );
+
+                // DAMM v2 packs the scheduler in the generic base fee factors
+                BaseFeeParameters {
+                    cliff_fee_numerator: self.cliff_fee_numerator,
+                    first_factor: scheduler.number_of_period,
+                    second_factor: scheduler.period_frequency.to_le_bytes(),
+                    third_factor: scheduler.reduction_factor,
+                    base_fee_mode: match scheduler.mode {
+                        FeeSchedulerMode::Linear => 0,
+                        FeeSchedulerMode::Exponential => 1,
+                    },
+                }
+            }
+        };
+
+        let dynamic_fee = match self.dynamic_fee {
+            None => None,
+            Some(dynamic_fee) => {
+                require!(
+                    dynamic_fee.filter_period < dynamic_fee.decay_period
+                        && dynamic_fee.reduction_factor as u64 <= BASIS_POINT_MAX
+                        && dynamic_fee.max_volatility_accumulator > 0
+                        && dynamic_fee.variable_fee_control > 0,
+                    ErrorCode::InvalidPoolFees
+                );
+
+                Some(DynamicFeeParameters {
+                    bin_step: BIN_STEP_BPS_DEFAULT,
+                    bin_step_u128: BIN_STEP_BPS_U128_DEFAULT,
+                    filter_period: dynamic_fee.filter_period,
+                    decay_period: dynamic_fee.decay_period,
+                    reduction_factor: dynamic_fee.reduction_factor,
+                    max_volatility_accumulator: dynamic_fee.max_volatility_accumulator,
+                    variable_fee_control: dynamic_fee.variable_fee_control,
+                })
+            }
+        };
+
+        Ok(PoolFeeParameters {
+            base_fee,
+            padding: [0u8; 3],
+            dynamic_fee,
+        })
+    }
+
+    /// `fee * (1 - reduction_bps / 10_000) ^ periods`, in Q64.64 by squaring and rounded down.
+    fn exponential_fee(fee: u64, reduction_bps: u64, periods: u16) -> Result<u64> {
+        let one = U256::from(1_u8) << 64;
+        let mut base = U256::from(BASIS_POINT_MAX - reduction_bps)
+            .checked_mul(one)
+            .and_then(|x| x.checked_div(U256::from(BASIS_POINT_MAX)))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let mut factor = one;
+        let mut periods = periods;
+
+        while periods > 0 {
+            if periods & 1 == 1 {
+                factor = (factor * base) >> 64;
+            }
+            base = (base * base) >> 64;
+            periods >>= 1;
+        }
+
+        let fee = (U256::from(fee) * factor) >> 64;
+
+        Ok(u64::try_from(fee).map_err(|_| ProgramError::ArithmeticOverflow)?)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum PriceRange {
+    /// Band around the price estimated from the deposit, from `price * (1 - lower_bps)`
+    /// to `price * (1 + upper_bps)`, clamped to the DAMM v2 limits
+    Deviation { lower_bps: u16, upper_bps: u32 },
+    /// Explicit sqrt prices in Q64.64, `MIN_SQRT_PRICE` and `MAX_SQRT_PRICE` for a full range pool
+    SqrtPrice { sqrt_min_price: u128, sqrt_max_price: u128 },
+}
+
+impl PriceRange {
+    /// Sqrt price bounds of the range in Q64.64, a deviation band is taken around `estimated_sqrt`.
+    pub fn sqrt_prices(&self, estimated_sqrt: u128) -> Result<(u128, u128)> {
+        Ok(match *self {
+            PriceRange::Deviation { lower_bps, upper_bps } => {
+                require!(
+                    lower_bps > 0 && lower_bps <= 10_000 && upper_bps > 0,
+                    ErrorCode::InvalidPriceRange
+                );
+
+                (
+                    InitializePool::scale_sqrt_price(estimated_sqrt, 10_000 - lower_bps as u64)?.max(MIN_SQRT_PRICE),
+                    InitializePool::scale_sqrt_price(estimated_sqrt, 10_000 + upper_bps as u64)?.min(MAX_SQRT_PRICE),
+                )
+            }
+            PriceRange::SqrtPrice { sqrt_min_price, sqrt_max_price } => (sqrt_min_price, sqrt_max_price),
+        })
+    }
+}
+
+/// Bumps of the distribution accounts of a launch
+pub struct DistributionBumps {
+    pub distribution_policy: u8,
+    pub distribution_state: u8,
+    pub fee_index: u8,
+    pub fee_ledger: u8,
+}
+
+/// Sets the distribution accounts of a new launch to their defaults, for `initialize_pool` and
+/// the launches brought over by `initialize_launch_accounts`.
+pub fn populate_distribution_accounts(
+    distribution_policy: &mut Account<DistributionPolicy>,
+    distribution_state: &mut Account<DistributionState>,
+    fee_index: &mut Account<FeeIndex>,
+    fee_ledger: &mut Account<FeeLedger>,
+    bumps: DistributionBumps,
+) -> Result<()> {
+    distribution_policy.set_inner(DistributionPolicy::new([bumps.distribution_policy]));
+    distribution_state.set_inner(DistributionState::new([bumps.distribution_state]));
+    fee_index.set_inner(FeeIndex::new([bumps.fee_index]));
+    fee_ledger.set_inner(FeeLedger::new([bumps.fee_ledger]));
 
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializePool<'info> {
-    #[account(mut)]
+    // The launch terms are instruction arguments, so only the position manager can open a launch
+    #[account(mut, address = global_config.position_manager @ ErrorCode::Unauthorized)]
     pub payer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
     #[account(
         init,
         payer = payer,
@@ -30,6 +257,61 @@ pub struct InitializePool<'info> {
         bump
     )]
     pub creator: Account<'info, Creator>,
+    #[account(
+        init,
+        payer = payer,
+        space = LaunchConfig::DISCRIMINATOR.len() + LaunchConfig::INIT_SPACE,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = DistributionPolicy::DISCRIMINATOR.len() + DistributionPolicy::INIT_SPACE,
+        seeds = [
+            b"distribution_policy",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        init,
+        payer = payer,
+        space = DistributionState::DISCRIMINATOR.len() + DistributionState::INIT_SPACE,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        init,
+        payer = payer,
+        space = FeeIndex::DISCRIMINATOR.len() + FeeIndex::INIT_SPACE,
+        seeds = [
+            b"fee_index",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_index: Box<Account<'info, FeeIndex>>,
+    #[account(
+        init,
+        payer = payer,
+        space = FeeLedger::DISCRIMINATOR.len() + FeeLedger::INIT_SPACE,
+        seeds = [
+            b"fee_ledger",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
     #[account(
         mut,
         seeds = [
@@ -43,7 +325,7 @@ pub struct InitializePool<'info> {
     #[account(
         mut,
         seeds = [
-            POSITION_NFT_ACCOUNT_PREFIX.as_ref(), 
+            POSITION_NFT_ACCOUNT_PREFIX, 
             position_nft_mint.key().as_ref()
         ],
         seeds::program = cp_amm::ID,
@@ -59,7 +341,7 @@ pub struct InitializePool<'info> {
     #[account(
         mut,
         seeds = [
-            CUSTOMIZABLE_POOL_PREFIX.as_ref(),
+            CUSTOMIZABLE_POOL_PREFIX,
             &max_key(&mint_a.key(), &mint_b.key()),
             &min_key(&mint_a.key(), &mint_b.key()),
         ],
@@ -71,7 +353,7 @@ pub struct InitializePool<'info> {
     #[account(
         mut,
         seeds = [
-            POSITION_PREFIX.as_ref(),
+            POSITION_PREFIX,
             position_nft_mint.key().as_ref()
         ],
         seeds::program = cp_amm::ID,
@@ -79,12 +361,14 @@ pub struct InitializePool<'info> {
     )]
     /// CHECK: Account checked and initialized by DAMMV2
     pub position: UncheckedAccount<'info>,
-    pub mint_a: Box<Account<'info, Mint>>,
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_a_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_b_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
         seeds = [
-            TOKEN_VAULT_PREFIX.as_ref(),
+            TOKEN_VAULT_PREFIX,
             mint_a.key().as_ref(),
             pool.key().as_ref(),
         ],
@@ -96,7 +380,7 @@ pub struct InitializePool<'info> {
     #[account(
         mut,
         seeds = [
-            TOKEN_VAULT_PREFIX.as_ref(),
+            TOKEN_VAULT_PREFIX,
             mint_b.key().as_ref(),
             pool.key().as_ref(),
         ],
@@ -109,16 +393,19 @@ pub struct InitializePool<'info> {
     #[account(
         mut,
         associated_token::mint = mint_a,
+        associated_token::token_program = token_a_program,
         associated_token::authority = creator,
     )]
-    payer_token_a: Box<Account<'info, TokenAccount>>,
+    payer_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         associated_token::mint = mint_b,
+        associated_token::token_program = token_b_program,
         associated_token::authority = creator,
     )]
-    payer_token_b: Box<Account<'info, TokenAccount>>,
-    token_program: Program<'info, Token>,
+    payer_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_a_program: Interface<'info, TokenInterface>,
+    token_b_program: Interface<'info, TokenInterface>,
     token_2022_program: Program<'info, Token2022>,
     system_program: Program<'info, System>,
     #[account(
@@ -127,7 +414,7 @@ pub struct InitializePool<'info> {
         bump
     )]
     /// CHECK: Account checked and initialized by DAMMV2
-    pub event_authority: AccountInfo<'info>,
+    pub dammv2_event_authority: AccountInfo<'info>,
     #[account(address = cp_amm::ID)]
     /// CHECK: Self-CPI will fail if the program is not the current program
     pub dammv2_program: AccountInfo<'info>,
@@ -136,13 +423,68 @@ pub struct InitializePool<'info> {
 impl<'info> InitializePool<'info> {
     pub fn populate_creator(&mut self, bump: [u8; 1]) -> Result<()> {
         self.creator.set_inner(Creator {
-            bump,
+            version: Creator::VERSION,
             streams: 0,
+            legacy_streams: 0,
+            last_claimed_at: 0,
+            // Set by `deposit` when the investor position is created
+            position_owner_bump: [0],
+            bump,
         });
 
         Ok(())
     }
-    
+
+    /// # Populate Launch Config
+    ///
+    /// Stores the launch economics after checking them against token B.
+    pub fn populate_launch_config(&mut self, args: &LaunchConfigArgs, bump: [u8; 1]) -> Result<()> {
+        require!(
+            args.commitment_bps > 0 && args.commitment_bps <= 10_000,
+            ErrorCode::InvalidCommitment
+        );
+        require_eq!(args.decimals, self.mint_b.decimals, ErrorCode::InvalidDecimals);
+        require!(
+            args.pool_token_amount > 0 && args.pool_token_amount <= self.mint_b.supply,
+            ErrorCode::InvalidPoolAmount
+        );
+        require_keys_neq!(args.treasury, Pubkey::default(), ErrorCode::InvalidTreasury);
+
+        // An alpha vault buys before activation, so it needs an activation point ahead
+        if let Some(activation_point) = args.activation_point {
+            require_gt!(activation_point, args.activation_type.now()?, ErrorCode::InvalidActivation);
+        }
+        require!(!args.has_alpha_vault || args.activation_point.is_some(), ErrorCode::InvalidActivation);
+
+        self.launch_config.set_inner(LaunchConfig {
+            commitment_bps: args.commitment_bps,
+            pool_token_amount: args.pool_token_amount,
+            decimals: args.decimals,
+            paused: false,
+            pause_reason: 0,
+            stream_permissions: args.stream_permissions,
+            treasury: args.treasury,
+            total_deposited: 0,
+            legacy_streams_pending: 0,
+            activation_type: args.activation_type,
+            activation_point: args.activation_point,
+            has_alpha_vault: args.has_alpha_vault,
+            bump,
+        });
+
+        Ok(())
+    }
+
+    pub fn populate_distribution_accounts(&mut self, bumps: DistributionBumps) -> Result<()> {
+        populate_distribution_accounts(
+            &mut self.distribution_policy,
+            &mut self.distribution_state,
+            &mut self.fee_index,
+            &mut self.fee_ledger,
+            bumps,
+        )
+    }
+
     pub fn delegate_token_account(&mut self, bump: [u8; 1]) -> Result<()> {
         let signer_seeds: [&[&[u8]];1] = [&[
             b"creator".as_ref(),
@@ -152,7 +494,7 @@ impl<'info> InitializePool<'info> {
 
         approve(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
+                self.token_a_program.to_account_info(),
                 Approve {
                     to: self.payer_token_a.to_account_info(),
                     delegate: self.payer.to_account_info(),
@@ -165,7 +507,7 @@ impl<'info> InitializePool<'info> {
 
         approve(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
+                self.token_b_program.to_account_info(),
                 Approve {
                     to: self.payer_token_b.to_account_info(),
                     delegate: self.payer.to_account_info(),
@@ -188,7 +530,7 @@ impl<'info> InitializePool<'info> {
 
         revoke(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
+                self.token_a_program.to_account_info(),
                 Revoke {
                     source: self.payer_token_a.to_account_info(),
                     authority: self.creator.to_account_info(),
@@ -199,7 +541,7 @@ impl<'info> InitializePool<'info> {
 
         revoke(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
+                self.token_b_program.to_account_info(),
                 Revoke {
                     source: self.payer_token_b.to_account_info(),
                     authority: self.creator.to_account_info(),
@@ -211,7 +553,7 @@ impl<'info> InitializePool<'info> {
         Ok(())
     }
 
-    pub fn create_pool(&mut self, position_nft_mint_bump: [u8;1]) -> Result<()> {
+    pub fn create_pool(&mut self, args: &LaunchConfigArgs, position_nft_mint_bump: [u8;1]) -> Result<PoolInitialized> {
         let accounts = InitializeCustomizablePoolCtx {
             creator: self.creator.to_account_info(),
             position_nft_mint: self.position_nft_mint.to_account_info(),
@@ -226,11 +568,11 @@ impl<'info> InitializePool<'info> {
             token_b_vault: self.token_b_vault.to_account_info(),
             payer_token_a: self.payer_token_a.to_account_info(),
             payer_token_b: self.payer_token_b.to_account_info(),
-            token_a_program: self.token_program.to_account_info(),
-            token_b_program: self.token_program.to_account_info(),
+            token_a_program: self.token_a_program.to_account_info(),
+            token_b_program: self.token_b_program.to_account_info(),
             token_2022_program: self.token_2022_program.to_account_info(),
             system_program: self.system_program.to_account_info(),
-            event_authority: self.event_authority.to_account_info(),
+            event_authority: self.dammv2_event_authority.to_account_info(),
             program: self.dammv2_program.to_account_info()
         };
     
@@ -246,27 +588,19 @@ impl<'info> InitializePool<'info> {
                 &signer_seeds
         );
     
-        let pool_fees = PoolFeeParameters {
-            base_fee: BaseFeeParameters {
-                cliff_fee_numerator: 2_500_000,
-                first_factor: 0,
-                second_factor: [0u8;8],
-                third_factor: 0,
-                base_fee_mode: 0,
-            },
-            padding: [0u8;3],
-            dynamic_fee: None,
-        };
+        let pool_fees = args.pool_fees.pool_fee_parameters()?;
     
+        let pool_amount = self.launch_config.pool_token_amount;
+
         let amount_a = self.payer_token_a.amount
-            .checked_mul(COMMITMENT_IN_BPS as u64)
+            .checked_mul(self.launch_config.commitment_bps as u64)
             .and_then(|x| x.checked_div(10_000))
             .ok_or(ProgramError::ArithmeticOverflow)?;
     
         // Step 1: Estimate the initial sqrt price from token ratio
         let estimated_price_ratio = U256::from(amount_a)
             .safe_shl(128)?
-            .checked_div(U256::from(POOL_AMOUNT))
+            .checked_div(U256::from(pool_amount))
             .ok_or(ProgramError::ArithmeticOverflow)?;
         
         let estimated_sqrt = u128::try_from(
@@ -274,51 +608,37 @@ impl<'info> InitializePool<'info> {
                 .ok_or(ProgramError::ArithmeticOverflow)?
         ).map_err(|_| ProgramError::ArithmeticOverflow)?;
     
-        // Step 2: Create ±30% price range
-        let range_sqrt_min = u128::try_from(
-            U256::from(estimated_sqrt)
-                .checked_mul(U256::from(8367_u32))  // sqrt(0.7) * 10000
-                .and_then(|x| x.checked_div(U256::from(10000_u32)))
-                .ok_or(ProgramError::ArithmeticOverflow)?
-        ).map_err(|_| ProgramError::ArithmeticOverflow)?
-            .max(MIN_SQRT_PRICE);
-    
-        let range_sqrt_max = u128::try_from(
-            U256::from(estimated_sqrt)
-                .checked_mul(U256::from(11402_u32))  // sqrt(1.3) * 10000
-                .and_then(|x| x.checked_div(U256::from(10000_u32)))
-                .ok_or(ProgramError::ArithmeticOverflow)?
-        ).map_err(|_| ProgramError::ArithmeticOverflow)?
-            .min(MAX_SQRT_PRICE);
+        // Step 2: Create the price range
+        let (range_sqrt_min, range_sqrt_max) = args.price_range.sqrt_prices(estimated_sqrt)?;
     
         // Validate range
         require!(
             range_sqrt_min < range_sqrt_max,
-            ErrorCode::InvalidAmount
+            ErrorCode::InvalidPriceRange
         );
         require!(
             range_sqrt_min >= MIN_SQRT_PRICE && range_sqrt_max <= MAX_SQRT_PRICE,
-            ErrorCode::InvalidAmount
+            ErrorCode::InvalidPriceRange
         );
     
-        // Step 3: Calculate the actual init price for exact ratio within the ±30% range
+        // Step 3: Calculate the actual init price for exact ratio within the range
         let sqrt_price = Self::calculate_init_price(
             amount_a,
-            POOL_AMOUNT,
+            pool_amount,
             range_sqrt_min,
             range_sqrt_max
         )?;
         
-        // Validate sqrt_price is within range
+        // Validate sqrt_price is strictly within range, both sides of the deposit are used
         require!(
-            sqrt_price >= range_sqrt_min && sqrt_price <= range_sqrt_max,
-            ErrorCode::InvalidAmount
+            sqrt_price > range_sqrt_min && sqrt_price < range_sqrt_max,
+            ErrorCode::InvalidPriceRange
         );
     
         // Step 4: Calculate correct liquidity for exact deposit amounts
         let liquidity = Self::calculate_liquidity(
             amount_a,
-            POOL_AMOUNT,
+            pool_amount,
             sqrt_price,
             range_sqrt_min,
             range_sqrt_max
@@ -331,18 +651,47 @@ impl<'info> InitializePool<'info> {
             pool_fees,
             sqrt_min_price: range_sqrt_min,
             sqrt_max_price: range_sqrt_max,
-            has_alpha_vault: false,
+            has_alpha_vault: args.has_alpha_vault,
             liquidity,
             sqrt_price, 
-            activation_type: 0,
+            activation_type: args.activation_type as u8,
             collect_fee_mode: CollectFeeMode::OnlyB.into(),
-            activation_point: None,
+            activation_point: args.activation_point,
         };
     
         cp_amm::cpi::initialize_customizable_pool(
             ctx, 
             params
-        )
+        )?;
+
+        Ok(PoolInitialized {
+            launch: self.creator.key(),
+            epoch: self.distribution_state.epoch,
+            pool: self.pool.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            token_a_amount: amount_a,
+            token_b_amount: pool_amount,
+        })
+    }
+
+    /// Scales `sqrt_price` to the sqrt of `price * factor_bps / 10_000`: sqrt(factor) is
+    /// computed in Q64.64 with U256 math and the result is rounded down.
+    pub fn scale_sqrt_price(sqrt_price: u128, factor_bps: u64) -> Result<u128> {
+        let factor = U256::from(factor_bps)
+            .safe_shl(128)?
+            .checked_div(U256::from(10_000_u32))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let sqrt_factor = sqrt_u256(factor).ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let scaled = U256::from(sqrt_price)
+            .checked_mul(sqrt_factor)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            >> 64;
+
+        // Anything above u128 is clamped to `MAX_SQRT_PRICE` anyway
+        Ok(u128::try_from(scaled).unwrap_or(u128::MAX))
     }
 
     pub fn calculate_init_price(
@@ -406,4 +755,150 @@ impl<'info> InitializePool<'info> {
 
         u128::try_from(liquidity).map_err(|_| ProgramError::ArithmeticOverflow.into())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Price 1 in Q64.64
+    const ONE: u128 = 1 << 64;
+
+    #[test]
+    fn scales_like_the_old_multipliers() {
+        for sqrt_price in [ONE, ONE * 5 / 2, MAX_SQRT_PRICE / 3] {
+            // sqrt(0.7) ~ 0.8367 and sqrt(1.3) ~ 1.1402, which rounded up
+            for (factor_bps, multiplier) in [(7_000, 8_367), (13_000, 11_402)] {
+                let scaled = InitializePool::scale_sqrt_price(sqrt_price, factor_bps).unwrap();
+                let old = sqrt_price * multiplier / 10_000;
+
+                assert!(scaled <= old);
+                assert!(old - scaled <= old / 10_000);
+            }
+        }
+    }
+
+    #[test]
+    fn scales_to_the_exact_sqrt() {
+        // floor(sqrt(0.7) * 2^64) and floor(sqrt(1.3) * 2^64)
+        assert_eq!(InitializePool::scale_sqrt_price(ONE, 7_000).unwrap(), 15_433_653_386_177_134_321);
+        assert_eq!(InitializePool::scale_sqrt_price(ONE, 13_000).unwrap(), 21_032_524_265_936_792_269);
+        assert_eq!(InitializePool::scale_sqrt_price(ONE, 10_000).unwrap(), ONE);
+    }
+
+    #[test]
+    fn full_lower_deviation_clamps_to_min_price() {
+        assert_eq!(InitializePool::scale_sqrt_price(ONE, 0).unwrap(), 0);
+
+        let range = PriceRange::Deviation { lower_bps: 10_000, upper_bps: 3_000 };
+        let (sqrt_min_price, sqrt_max_price) = range.sqrt_prices(ONE).unwrap();
+
+        assert_eq!(sqrt_min_price, MIN_SQRT_PRICE);
+        assert_eq!(sqrt_max_price, 21_032_524_265_936_792_269);
+    }
+
+    #[test]
+    fn overflow_clamps_to_max_price() {
+        assert_eq!(InitializePool::scale_sqrt_price(u128::MAX, 40_000).unwrap(), u128::MAX);
+
+        let range = PriceRange::Deviation { lower_bps: 1, upper_bps: u32::MAX };
+        let (_, sqrt_max_price) = range.sqrt_prices(MAX_SQRT_PRICE).unwrap();
+
+        assert_eq!(sqrt_max_price, MAX_SQRT_PRICE);
+    }
+
+    fn pool_fees(cliff_fee_numerator: u64, mode: FeeSchedulerMode, number_of_period: u16, reduction_factor: u64) -> PoolFeeArgs {
+        PoolFeeArgs {
+            cliff_fee_numerator,
+            fee_scheduler: Some(FeeSchedulerArgs {
+                mode,
+                number_of_period,
+                period_frequency: 60,
+                reduction_factor,
+            }),
+            dynamic_fee: None,
+        }
+    }
+
+    fn dynamic_fees(filter_period: u16, decay_period: u16) -> PoolFeeArgs {
+        PoolFeeArgs {
+            cliff_fee_numerator: 10_000_000,
+            fee_scheduler: None,
+            dynamic_fee: Some(DynamicFeeArgs {
+                filter_period,
+                decay_period,
+                reduction_factor: 5_000,
+                max_volatility_accumulator: 14_460_000,
+                variable_fee_control: 5_000,
+            }),
+        }
+    }
+
+    #[test]
+    fn exponential_fee_matches_hand_computed_values() {
+        // 0.9 isn't exact in Q64.64, the fee is rounded down
+        assert_eq!(PoolFeeArgs::exponential_fee(100_000_000, 1_000, 1).unwrap(), 89_999_999);
+        assert_eq!(PoolFeeArgs::exponential_fee(100_000_000, 1_000, 2).unwrap(), 80_999_999);
+        // 1e8 * 0.9^10 = 34_867_844.01
+        assert_eq!(PoolFeeArgs::exponential_fee(100_000_000, 1_000, 10).unwrap(), 34_867_844);
+        // 1e7 * 0.99^100 = 3_660_323.41
+        assert_eq!(PoolFeeArgs::exponential_fee(10_000_000, 100, 100).unwrap(), 3_660_323);
+        assert_eq!(PoolFeeArgs::exponential_fee(500_000_000, 5_000, 3).unwrap(), 62_500_000);
+        assert_eq!(PoolFeeArgs::exponential_fee(100_000_000, 0, 5).unwrap(), 100_000_000);
+    }
+
+    #[test]
+    fn rejects_linear_schedules_below_zero() {
+        assert!(pool_fees(1_000_000, FeeSchedulerMode::Linear, 11, 100_000).pool_fee_parameters().is_err());
+        assert!(pool_fees(1_000_000, FeeSchedulerMode::Linear, u16::MAX, u64::MAX).pool_fee_parameters().is_err());
+    }
+
+    #[test]
+    fn schedules_may_end_at_the_min_fee() {
+        let cliff = MIN_FEE_NUMERATOR + 10 * 90_000;
+        assert!(pool_fees(cliff, FeeSchedulerMode::Linear, 10, 90_000).pool_fee_parameters().is_ok());
+        assert!(pool_fees(cliff, FeeSchedulerMode::Linear, 10, 90_001).pool_fee_parameters().is_err());
+
+        // Halved three times down to the min fee, a fourth time below it
+        let cliff = MIN_FEE_NUMERATOR * 8;
+        assert!(pool_fees(cliff, FeeSchedulerMode::Exponential, 3, 5_000).pool_fee_parameters().is_ok());
+        assert!(pool_fees(cliff, FeeSchedulerMode::Exponential, 4, 5_000).pool_fee_parameters().is_err());
+    }
+
+    #[test]
+    fn rejects_cliff_fees_out_of_bounds() {
+        for cliff in [MIN_FEE_NUMERATOR - 1, MAX_FEE_NUMERATOR + 1] {
+            let args = PoolFeeArgs { cliff_fee_numerator: cliff, fee_scheduler: None, dynamic_fee: None };
+            assert!(args.pool_fee_parameters().is_err());
+        }
+    }
+
+    #[test]
+    fn packs_the_scheduler_in_the_base_fee() {
+        let base_fee = pool_fees(10_000_000, FeeSchedulerMode::Exponential, 12, 500)
+            .pool_fee_parameters()
+            .unwrap()
+            .base_fee;
+
+        assert_eq!(base_fee.cliff_fee_numerator, 10_000_000);
+        assert_eq!(base_fee.first_factor, 12);
+        assert_eq!(u64::from_le_bytes(base_fee.second_factor), 60);
+        assert_eq!(base_fee.third_factor, 500);
+        assert_eq!(base_fee.base_fee_mode, 1);
+    }
+
+    #[test]
+    fn dynamic_fee_filter_period_below_decay_period() {
+        assert!(dynamic_fees(10, 120).pool_fee_parameters().is_ok());
+        assert!(dynamic_fees(120, 120).pool_fee_parameters().is_err());
+        assert!(dynamic_fees(121, 120).pool_fee_parameters().is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_deviations() {
+        for (lower_bps, upper_bps) in [(0, 3_000), (10_001, 3_000), (3_000, 0)] {
+            let range = PriceRange::Deviation { lower_bps, upper_bps };
+            assert!(range.sqrt_prices(ONE).is_err());
+        }
+    }
+}
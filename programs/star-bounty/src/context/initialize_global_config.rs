@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::program::StarBounty;
+use crate::state::GlobalConfig;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = GlobalConfig::DISCRIMINATOR.len() + GlobalConfig::INIT_SPACE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, StarBounty>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeGlobalConfig<'info> {
+    /// # Initialize Global Config
+    ///
+    /// Only the upgrade authority can create the config and pick the first admin,
+    /// after that the admin is rotated with `propose_admin` / `accept_admin`.
+    /// Every role starts with the admin and cranking starts permissionless. The streams of the
+    /// v1 launches were sent by the old hardcoded admin, which is trusted as a sender next to
+    /// the admin.
+    pub fn initialize_global_config(&mut self, admin: Pubkey, bump: [u8; 1]) -> Result<()> {
+        let mut stream_senders = vec![GlobalConfig::LEGACY_STREAM_SENDER];
+        if admin != GlobalConfig::LEGACY_STREAM_SENDER {
+            stream_senders.push(admin);
+        }
+
+        self.global_config.set_inner(GlobalConfig {
+            admin,
+            pending_admin: None,
+            stream_manager: admin,
+            position_manager: admin,
+            pauser: admin,
+            treasury_manager: admin,
+            cranker: None,
+            paused: false,
+            pause_reason: 0,
+            stream_senders,
+            bump,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::context::{populate_distribution_accounts, DistributionBumps};
+use crate::state::{ActivationType, Creator, DistributionPolicy, DistributionState, FeeIndex, FeeLedger, GlobalConfig, LaunchConfig, StreamPermissions};
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LaunchAccountsArgs {
+    /// Share of the token A balance committed to the pool, in bps
+    pub commitment_bps: u16,
+    /// Amount of token B deposited in the pool
+    pub pool_token_amount: u64,
+    /// Owner of the token account receiving the creator remainder
+    pub treasury: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeLaunchAccounts<'info> {
+    #[account(mut, address = global_config.admin @ ErrorCode::Unauthorized)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        init,
+        payer = payer,
+        space = LaunchConfig::DISCRIMINATOR.len() + LaunchConfig::INIT_SPACE,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = DistributionPolicy::DISCRIMINATOR.len() + DistributionPolicy::INIT_SPACE,
+        seeds = [
+            b"distribution_policy",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        init,
+        payer = payer,
+        space = DistributionState::DISCRIMINATOR.len() + DistributionState::INIT_SPACE,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        init,
+        payer = payer,
+        space = FeeIndex::DISCRIMINATOR.len() + FeeIndex::INIT_SPACE,
+        seeds = [
+            b"fee_index",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_index: Box<Account<'info, FeeIndex>>,
+    #[account(
+        init,
+        payer = payer,
+        space = FeeLedger::DISCRIMINATOR.len() + FeeLedger::INIT_SPACE,
+        seeds = [
+            b"fee_ledger",
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeLaunchAccounts<'info> {
+    /// # Populate Launch Config
+    ///
+    /// Stores the launch economics of a launch brought over by `migrate_creator`, which only
+    /// rewrites `Creator`. Its v1 streams are added afterwards with `load_legacy_streams`, Y0 can't
+    /// be read and the launch can't be cranked until they all are.
+    pub fn populate_launch_config(&mut self, args: &LaunchAccountsArgs, bump: [u8; 1]) -> Result<()> {
+        require!(
+            args.commitment_bps > 0 && args.commitment_bps <= 10_000,
+            ErrorCode::InvalidCommitment
+        );
+        require!(
+            args.pool_token_amount > 0 && args.pool_token_amount <= self.mint.supply,
+            ErrorCode::InvalidPoolAmount
+        );
+        require_keys_neq!(args.treasury, Pubkey::default(), ErrorCode::InvalidTreasury);
+
+        self.launch_config.set_inner(LaunchConfig {
+            commitment_bps: args.commitment_bps,
+            pool_token_amount: args.pool_token_amount,
+            decimals: self.mint.decimals,
+            paused: false,
+            pause_reason: 0,
+            // Widened to the rights of the v1 streams as they're loaded
+            stream_permissions: StreamPermissions::default(),
+            treasury: args.treasury,
+            total_deposited: 0,
+            legacy_streams_pending: self.creator.legacy_streams,
+            // The pool of a v1 launch is already trading
+            activation_type: ActivationType::Slot,
+            activation_point: None,
+            has_alpha_vault: false,
+            bump,
+        });
+
+        Ok(())
+    }
+
+    pub fn populate_distribution_accounts(&mut self, bumps: DistributionBumps) -> Result<()> {
+        populate_distribution_accounts(
+            &mut self.distribution_policy,
+            &mut self.distribution_state,
+            &mut self.fee_index,
+            &mut self.fee_ledger,
+            bumps,
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Creator, GlobalConfig, LaunchConfig, StreamLedger};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitializeStreamLedger<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    /// CHECK: Checked against the creator stream PDA and deserialized in `initialize_stream_ledger`
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = StreamLedger::DISCRIMINATOR.len() + StreamLedger::INIT_SPACE,
+        seeds = [
+            b"stream_ledger",
+            metadata.key().as_ref(),
+        ],
+        bump
+    )]
+    pub stream_ledger: Account<'info, StreamLedger>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeStreamLedger<'info> {
+    /// # Initialize Stream Ledger
+    ///
+    /// Creates the ledger of stream `index`, which adds up every payout the stream receives from then on.
+    /// Permissionless and optional. Push distributions only update the ledgers passed after their
+    /// streams, pull claims update it once it exists.
+    pub fn initialize_stream_ledger(&mut self, index: u32, bump: [u8; 1]) -> Result<()> {
+        require_eq!(
+            self.creator.stream_metadata(&self.creator.key(), index),
+            self.metadata.key()
+        );
+
+        self.launch_config.load_stream(&self.global_config, &self.metadata, &self.mint_b.key(), self.creator.legacy_stream(index))?;
+
+        self.stream_ledger.set_inner(StreamLedger {
+            paid: 0,
+            last_paid_at: 0,
+            bump,
+        });
+
+        Ok(())
+    }
+}
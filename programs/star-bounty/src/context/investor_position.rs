@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use cp_amm::{const_pda::pool_authority::ID as POOL_AUTHORITY_ID, constants::seeds::{CUSTOMIZABLE_POOL_PREFIX, POSITION_NFT_ACCOUNT_PREFIX, POSITION_PREFIX, TOKEN_VAULT_PREFIX}, max_key, min_key};
+
+use crate::events::FeesClaimed;
+use crate::state::{Creator, DistributionState, FeeLedger};
+use crate::position::claim_investor_position_fees;
+
+/// Launch and DAMM v2 accounts of the investor position, nested by the distribution cranks
+#[derive(Accounts)]
+pub struct InvestorPosition<'info> {
+    #[account(mint::token_program = token_a_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_b_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        seeds = [
+            b"investor_fee_pos_owner", 
+            mint_b.key().as_ref()
+        ],
+        bump = creator.position_owner_bump[0],
+    )]
+    /// CHECK: Data-less PDA, only used to sign for the investor position
+    pub investor_fee_pos_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"position_mint", 
+            investor_fee_pos_owner.key().as_ref()
+        ],
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2. Must be either a Signer or CPI Signer
+    pub position_nft_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            POSITION_NFT_ACCOUNT_PREFIX, 
+            position_nft_mint.key().as_ref()
+        ],
+        seeds::program = cp_amm::ID,
+        bump
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub position_nft_account: UncheckedAccount<'info>,
+    #[account(
+        address = POOL_AUTHORITY_ID
+    )]
+    /// CHECK: Account safely bound by address constraint
+    pub pool_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            CUSTOMIZABLE_POOL_PREFIX,
+            &max_key(&mint_a.key(), &mint_b.key()),
+            &min_key(&mint_a.key(), &mint_b.key()),
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            POSITION_PREFIX,
+            position_nft_mint.key().as_ref()
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub position: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            mint_a.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub token_a_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_VAULT_PREFIX,
+            mint_b.key().as_ref(),
+            pool.key().as_ref(),
+        ],
+        seeds::program = cp_amm::ID,
+        bump,
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub token_b_vault: UncheckedAccount<'info>,
+    // Position owner's token accounts
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::token_program = token_a_program,
+        associated_token::authority = investor_fee_pos_owner,
+    )]
+    pub token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::token_program = token_b_program,
+        associated_token::authority = investor_fee_pos_owner,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_a_program: Interface<'info, TokenInterface>,
+    pub token_b_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [b"__event_authority"], 
+        seeds::program = cp_amm::ID,
+        bump
+    )]
+    /// CHECK: Account checked and initialized by DAMMV2
+    pub dammv2_event_authority: AccountInfo<'info>,
+    #[account(address = cp_amm::ID)]
+    /// CHECK: Self-CPI will fail if the program is not the current program
+    pub dammv2_program: AccountInfo<'info>,
+}
+
+impl<'info> InvestorPosition<'info> {
+    pub fn claim(
+        &self,
+        distribution_state: &mut DistributionState,
+        fee_ledger: &mut FeeLedger,
+    ) -> Result<FeesClaimed> {
+        let signer_seeds: [&[&[u8]];1] = [&[
+            b"investor_fee_pos_owner".as_ref(),
+            self.mint_b.to_account_info().key.as_ref(),
+            &self.creator.position_owner_bump
+        ]];
+
+        let accounts = cp_amm::cpi::accounts::ClaimPositionFeeCtx {
+            pool_authority: self.pool_authority.to_account_info(),
+            pool: self.pool.to_account_info(),
+            position: self.position.to_account_info(),
+            token_a_account: self.token_a_account.to_account_info(),
+            token_b_account: self.token_b_account.to_account_info(),
+            token_a_vault: self.token_a_vault.to_account_info(),
+            token_b_vault: self.token_b_vault.to_account_info(),
+            token_a_mint: self.mint_a.to_account_info(),
+            token_b_mint: self.mint_b.to_account_info(),
+            position_nft_account: self.position_nft_account.to_account_info(),
+            owner: self.investor_fee_pos_owner.to_account_info(),
+            token_a_program: self.token_a_program.to_account_info(),
+            token_b_program: self.token_b_program.to_account_info(),
+            event_authority: self.dammv2_event_authority.to_account_info(),
+            program: self.dammv2_program.to_account_info(),
+        };
+
+        claim_investor_position_fees(
+            accounts,
+            &signer_seeds,
+            distribution_state,
+            fee_ledger,
+            self.creator.key(),
+        )
+    }
+}
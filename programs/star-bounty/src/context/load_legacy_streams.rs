@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{read_stream, stream_closed, Creator, GlobalConfig, LaunchConfig, StreamPermissions};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct LoadLegacyStreams<'info> {
+    #[account(address = global_config.admin @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        mut,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref()
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+}
+
+impl<'info> LoadLegacyStreams<'info> {
+    /// # Load Legacy Streams
+    ///
+    /// Adds the next v1 streams of a migrated launch to its deposits and stream permissions,
+    /// passed as their `metadata` accounts in the remaining accounts in index order, starting at
+    /// the first stream not loaded yet. Call it until `legacy_streams_pending` is zero.
+    ///
+    /// Every v1 stream was created with `LEGACY_STREAM_AMOUNT` and top-ups enabled. A live stream
+    /// counts for its deposit up to that amount, so a top-up can't add to Y0, and a stream
+    /// Streamflow already closed counts for the whole of it.
+    pub fn load_legacy_streams(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let pending = self.launch_config.legacy_streams_pending as usize;
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() <= pending,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let first = self.creator.legacy_streams as usize - pending;
+        let mut stream_permissions = self.launch_config.stream_permissions;
+        let mut deposited = 0u64;
+
+        for (i, metadata) in remaining_accounts.iter().enumerate() {
+            require_keys_eq!(
+                self.creator.stream_metadata(&self.creator.key(), (first + i) as u32),
+                metadata.key()
+            );
+
+            let amount = if stream_closed(metadata) {
+                Creator::LEGACY_STREAM_AMOUNT
+            } else {
+                let stream = read_stream(&self.global_config, metadata, &self.mint.key(), true)?;
+
+                stream_permissions = stream_permissions.union(&StreamPermissions::from(&stream.ix));
+                stream.ix.net_amount_deposited
+            };
+
+            deposited = deposited.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        self.launch_config.stream_permissions = stream_permissions;
+        self.launch_config.total_deposited = self.launch_config.total_deposited.checked_add(deposited).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.launch_config.legacy_streams_pending -= remaining_accounts.len() as u8;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Creator, GlobalConfig, InvestorFeePositionOwnerPda};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct MigrateCreator<'info> {
+    #[account(mut, address = global_config.admin @ ErrorCode::Unauthorized)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// CHECK: Legacy layout, checked and rewritten in `migrate_creator`
+    pub creator: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = payer,
+        constraint = investor_fee_pos_owner.associated_mint == mint.key(),
+        seeds = [
+            b"investor_fee_pos_owner",
+            mint.key().as_ref()
+        ],
+        bump = investor_fee_pos_owner.bump[0],
+    )]
+    pub investor_fee_pos_owner: Account<'info, InvestorFeePositionOwnerPda>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateCreator<'info> {
+    /// # Migrate Creator
+    ///
+    /// Reallocs a v1 `Creator` to the current layout and moves `last_claimed_at` over from the
+    /// `InvestorFeePositionOwnerPda`, which is then closed. The PDA address keeps signing for the
+    /// DAMM v2 position through `Creator.position_owner_bump`, so the position NFT doesn't move.
+    /// Existing streams are recorded as `legacy_streams` to keep their single byte seed.
+    pub fn migrate_creator(&mut self) -> Result<()> {
+        let creator_info = self.creator.to_account_info();
+
+        let (streams, bump) = {
+            let data = creator_info.try_borrow_data()?;
+            require_eq!(data.len(), Creator::LEGACY_LEN, ErrorCode::AlreadyMigrated);
+            require!(data[..8] == *Creator::DISCRIMINATOR, ErrorCode::AlreadyMigrated);
+            (data[8], data[9])
+        };
+
+        let space = Creator::DISCRIMINATOR.len() + Creator::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+        let top_up = rent.saturating_sub(creator_info.lamports());
+
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: creator_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        creator_info.resize(space)?;
+
+        let creator = Creator {
+            version: Creator::VERSION,
+            streams: streams as u32,
+            legacy_streams: streams,
+            last_claimed_at: self.investor_fee_pos_owner.last_claimed_at,
+            position_owner_bump: self.investor_fee_pos_owner.bump,
+            bump: [bump],
+        };
+
+        let mut data = creator_info.try_borrow_mut_data()?;
+        creator.try_serialize(&mut &mut data[..])
+    }
+}
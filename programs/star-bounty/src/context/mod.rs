@@ -7,8 +7,77 @@ pub use swap::*;
 pub mod create_stream;
 pub use create_stream::*;
 
+pub mod create_streams_batch;
+pub use create_streams_batch::*;
+
 pub mod deposit;
 pub use deposit::*;
 
+// Nested by the distribution cranks, which import it with its generated client modules
+pub mod investor_position;
+
 pub mod claim_fees;
-pub use claim_fees::*;
\ No newline at end of file
+pub use claim_fees::*;
+
+pub mod preview_distribution;
+pub use preview_distribution::*;
+
+pub mod start_distribution;
+pub use start_distribution::*;
+
+pub mod distribute_page;
+pub use distribute_page::*;
+
+pub mod finalize_distribution;
+pub use finalize_distribution::*;
+
+pub mod abort_distribution;
+pub use abort_distribution::*;
+
+pub mod register_stream_checkpoint;
+pub use register_stream_checkpoint::*;
+
+pub mod accrue_fees;
+pub use accrue_fees::*;
+
+pub mod initialize_stream_ledger;
+pub use initialize_stream_ledger::*;
+
+pub mod claim_investor_fees;
+pub use claim_investor_fees::*;
+
+pub mod sweep_surplus;
+pub use sweep_surplus::*;
+
+pub mod update_distribution_policy;
+pub use update_distribution_policy::*;
+
+pub mod initialize_global_config;
+pub use initialize_global_config::*;
+
+pub mod transfer_admin;
+pub use transfer_admin::*;
+
+pub mod roles;
+pub use roles::*;
+
+pub mod pause;
+pub use pause::*;
+
+pub mod set_treasury;
+pub use set_treasury::*;
+
+pub mod migrate_creator;
+pub use migrate_creator::*;
+
+pub mod initialize_launch_accounts;
+pub use initialize_launch_accounts::*;
+
+pub mod load_legacy_streams;
+pub use load_legacy_streams::*;
+
+pub mod set_creator_fee_split;
+pub use set_creator_fee_split::*;
+
+pub mod claim_creator_position_fees;
+pub use claim_creator_position_fees::*;
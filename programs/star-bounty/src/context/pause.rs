@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::events::{Paused, Unpaused};
+use crate::state::{Creator, GlobalConfig, LaunchConfig};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GlobalPause<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> GlobalPause<'info> {
+    /// # Pause
+    ///
+    /// Halts every launch, signed by the pauser.
+    pub fn pause(&mut self, reason: u16) -> Result<Paused> {
+        require_keys_eq!(self.authority.key(), self.global_config.pauser, ErrorCode::Unauthorized);
+        require!(!self.global_config.paused, ErrorCode::Paused);
+
+        self.global_config.paused = true;
+        self.global_config.pause_reason = reason;
+
+        Ok(Paused { launch: None, reason })
+    }
+
+    /// # Unpause
+    ///
+    /// Resuming is reserved to the admin so a leaked pauser key can't undo a pause.
+    pub fn unpause(&mut self) -> Result<Unpaused> {
+        require_keys_eq!(self.authority.key(), self.global_config.admin, ErrorCode::Unauthorized);
+        require!(self.global_config.paused, ErrorCode::NotPaused);
+
+        self.global_config.paused = false;
+        self.global_config.pause_reason = 0;
+
+        Ok(Unpaused { launch: None })
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LaunchPause<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0]
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        mut,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+}
+
+impl<'info> LaunchPause<'info> {
+    /// # Pause Launch
+    ///
+    /// Halts a single launch, signed by the pauser.
+    pub fn pause_launch(&mut self, reason: u16) -> Result<Paused> {
+        require_keys_eq!(self.authority.key(), self.global_config.pauser, ErrorCode::Unauthorized);
+        require!(!self.launch_config.paused, ErrorCode::Paused);
+
+        self.launch_config.paused = true;
+        self.launch_config.pause_reason = reason;
+
+        Ok(Paused { launch: Some(self.creator.key()), reason })
+    }
+
+    /// # Unpause Launch
+    ///
+    /// Same as `unpause`, only the admin can resume a launch.
+    pub fn unpause_launch(&mut self) -> Result<Unpaused> {
+        require_keys_eq!(self.authority.key(), self.global_config.admin, ErrorCode::Unauthorized);
+        require!(self.launch_config.paused, ErrorCode::NotPaused);
+
+        self.launch_config.paused = false;
+        self.launch_config.pause_reason = 0;
+
+        Ok(Unpaused { launch: Some(self.creator.key()) })
+    }
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use cp_amm::state::{Pool, Position};
+use ruint::aliases::U256;
+
+use crate::context::investor_position::*;
+use crate::events::SkipReason;
+use crate::distribution::{EpochPayout, StreamTally};
+use crate::transfer_fee::transfer_fee;
+use crate::state::{DistributionMode, DistributionPhase, DistributionPolicy, DistributionState, GlobalConfig, LaunchConfig};
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StreamShare {
+    pub stream_index: u32,
+    pub recipient: Pubkey,
+    pub locked: u64,
+    /// Amount the stream would receive, its share less the transfer fee the remainder can't cover
+    pub share: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DistributionPreview {
+    /// Epoch the distribution would open
+    pub epoch: u64,
+    /// `claim_fees` fails with `CooldownActive` before this timestamp
+    pub cooldown_end: i64,
+    /// Set when `claim_fees` would skip the distribution on dust, the other fields show what it
+    /// would pay otherwise
+    pub skip: Option<SkipReason>,
+    /// Pending fees plus the fees still unclaimed in the investor position
+    pub fees: u64,
+    pub initial_locked: u64,
+    pub total_locked: u64,
+    pub f_locked_bps: u64,
+    pub eligible_investor_share_bps: u64,
+    pub owed: u64,
+    pub distributable: u64,
+    pub shares: Vec<StreamShare>,
+    pub distributed: u64,
+    /// Transfer fees withheld on the payouts, paid out of the creator remainder as far as it goes
+    pub transfer_fees: u64,
+    pub investor_reserve: u64,
+    pub creator_remainder: u64,
+}
+
+#[derive(Accounts)]
+pub struct PreviewDistribution<'info> {
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub investor_position: InvestorPosition<'info>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        constraint = distribution_policy.mode == DistributionMode::Push @ ErrorCode::InvalidDistributionMode,
+        seeds = [
+            b"distribution_policy",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        constraint = distribution_state.phase == DistributionPhase::Idle @ ErrorCode::DistributionInProgress,
+        seeds = [
+            b"distribution",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+}
+
+impl<'info> PreviewDistribution<'info> {
+    /// # Preview Distribution
+    ///
+    /// Computes what `claim_fees` would distribute now, with the same remaining accounts, without
+    /// moving any token. The fees still unclaimed in the investor position are read from the DAMM v2
+    /// pool and position. The preview is returned as return data.
+    pub fn preview_distribution(&self, remaining_accounts: &[AccountInfo<'info>]) -> Result<DistributionPreview> {
+        let streams = self.investor_position.creator.streams as usize;
+
+        // `[metadata, recipient_tokens]` pairs, optionally followed by the stream ledgers `claim_fees` would update
+        require!(
+            remaining_accounts.len() >= streams * 2 && remaining_accounts.len() <= streams * 3,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let policy = &self.distribution_policy;
+
+        let reserve = self.distribution_state.investor_reserve;
+        let fees = self.distribution_state.pending_fees
+            .checked_add(self.unclaimed_fees()?)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let cooldown_end = self.investor_position.creator.last_claimed_at
+            .checked_add(policy.cooldown)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let skip = (fees < policy.dust_threshold).then_some(SkipReason::Dust);
+
+        let tally = StreamTally::load(&self.global_config, &self.investor_position.creator, &self.launch_config, &self.investor_position.mint_b.key(), remaining_accounts, now)?;
+        let payout = EpochPayout::compute(policy, &self.investor_position.mint_b.to_account_info(), fees, reserve, &tally)?;
+
+        let (f_locked_bps, eligible_investor_share_bps) = policy.investor_share_bps(tally.total_locked, tally.initial_locked)?;
+
+        let shares = (0..streams)
+            .map(|i| StreamShare {
+                stream_index: i as u32,
+                recipient: tally.recipients[i],
+                locked: tally.locked[i],
+                share: payout.payouts[i].received,
+            })
+            .collect();
+
+        Ok(DistributionPreview {
+            epoch: self.distribution_state.epoch.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?,
+            cooldown_end,
+            skip,
+            fees,
+            initial_locked: tally.initial_locked,
+            total_locked: tally.total_locked,
+            f_locked_bps,
+            eligible_investor_share_bps,
+            owed: payout.owed,
+            distributable: payout.distributable,
+            shares,
+            distributed: payout.distributed,
+            transfer_fees: payout.transfer_fees,
+            investor_reserve: payout.investor_reserve,
+            creator_remainder: payout.treasury_amount,
+        })
+    }
+
+    /// Token B fees the investor position would get from `claim_position_fee` now, less the fee
+    /// the mint withholds on the transfer out of the pool vault
+    fn unclaimed_fees(&self) -> Result<u64> {
+        let pool = Pool::try_deserialize(&mut &self.investor_position.pool.try_borrow_data()?[..])?;
+        let mut position = Position::try_deserialize(&mut &self.investor_position.position.try_borrow_data()?[..])?;
+
+        position.update_fee(
+            U256::from_le_bytes(pool.fee_a_per_liquidity),
+            U256::from_le_bytes(pool.fee_b_per_liquidity),
+        )?;
+
+        let fees = position.fee_b_pending;
+        let withheld = transfer_fee(&self.investor_position.mint_b.to_account_info(), fees)?;
+
+        Ok(fees.checked_sub(withheld).ok_or(ProgramError::ArithmeticOverflow)?)
+    }
+}
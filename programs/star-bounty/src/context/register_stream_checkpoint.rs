@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Creator, FeeIndex, GlobalConfig, LaunchConfig, StreamCheckpoint, StreamStatus};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RegisterStreamCheckpoint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_index",
+            creator.key().as_ref(),
+        ],
+        bump = fee_index.bump[0],
+    )]
+    pub fee_index: Account<'info, FeeIndex>,
+    /// CHECK: Checked against the creator stream PDA and deserialized in `register_stream_checkpoint`, may be closed already
+    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = StreamCheckpoint::DISCRIMINATOR.len() + StreamCheckpoint::INIT_SPACE,
+        seeds = [
+            b"stream_checkpoint",
+            metadata.key().as_ref(),
+        ],
+        bump
+    )]
+    pub stream_checkpoint: Account<'info, StreamCheckpoint>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterStreamCheckpoint<'info> {
+    /// # Register Stream Checkpoint
+    ///
+    /// Enters stream `index` in the pull distribution. Permissionless, and required for every
+    /// stream, including closed ones, before `accrue_fees` runs. The weight starts at what the
+    /// stream has locked now and the stream only earns the fees accrued after it's registered.
+    pub fn register_stream_checkpoint(&mut self, index: u32, bump: [u8; 1]) -> Result<()> {
+        require_eq!(
+            self.creator.stream_metadata(&self.creator.key(), index),
+            self.metadata.key()
+        );
+
+        let status = self.launch_config.load_stream_status(&self.global_config, &self.metadata, &self.mint_b.key(), self.creator.legacy_stream(index))?;
+        let now = Clock::get()?.unix_timestamp;
+
+        // A stream that isn't live never earns, there's nothing to pay it
+        let recipient_tokens = match &status {
+            StreamStatus::Live(stream) => stream.recipient_tokens,
+            _ => Pubkey::default(),
+        };
+
+        let mut checkpoint = StreamCheckpoint {
+            fee_per_locked_unit: self.fee_index.fee_per_locked_unit,
+            weight: 0,
+            pending: 0,
+            recipient_tokens,
+            terminated: false,
+            bump,
+        };
+        checkpoint.refresh(&mut self.fee_index, &status, now as u64)?;
+
+        self.fee_index.registered = self.fee_index.registered.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.stream_checkpoint.set_inner(checkpoint);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{RoleGranted, RoleRevoked, StreamSenderAdded, StreamSenderRemoved};
+use crate::state::{GlobalConfig, Role};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageRole<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> ManageRole<'info> {
+    /// # Grant Role
+    ///
+    /// Assigns `role` to `account`, replacing the previous holder. Stream managers are also added
+    /// to the stream senders, so the streams of former managers stay valid until the admin
+    /// removes them.
+    pub fn grant_role(&mut self, role: Role, account: Pubkey) -> Result<(Option<StreamSenderAdded>, RoleGranted)> {
+        require_keys_neq!(account, Pubkey::default(), ErrorCode::InvalidRoleAccount);
+
+        self.global_config.set_role(role, account);
+        let sender_added = role == Role::StreamManager && self.global_config.add_stream_sender(account)?;
+
+        Ok((sender_added.then_some(StreamSenderAdded { sender: account }), RoleGranted { role, account }))
+    }
+
+    /// # Revoke Role
+    ///
+    /// Clears `role`. Revoking the cranker makes `claim_fees` permissionless again.
+    pub fn revoke_role(&mut self, role: Role) -> Result<RoleRevoked> {
+        let account = self.global_config.role(role);
+        require_keys_neq!(account, Pubkey::default(), ErrorCode::RoleNotAssigned);
+
+        self.global_config.set_role(role, Pubkey::default());
+
+        Ok(RoleRevoked { role, account })
+    }
+
+    /// # Add Stream Sender
+    ///
+    /// Trusts the streams sent by `sender` again, e.g. a former stream manager removed to make
+    /// room. Adding a trusted sender does nothing.
+    pub fn add_stream_sender(&mut self, sender: Pubkey) -> Result<Option<StreamSenderAdded>> {
+        require_keys_neq!(sender, Pubkey::default(), ErrorCode::InvalidRoleAccount);
+
+        let added = self.global_config.add_stream_sender(sender)?;
+
+        Ok(added.then_some(StreamSenderAdded { sender }))
+    }
+
+    /// # Remove Stream Sender
+    ///
+    /// Stops trusting the streams sent by `sender`, for a compromised key or to make room
+    /// for a new stream manager. Distributions of the launches holding streams it sent fail
+    /// with `InvalidStreamSender` until it's added back.
+    pub fn remove_stream_sender(&mut self, sender: Pubkey) -> Result<StreamSenderRemoved> {
+        self.global_config.remove_stream_sender(sender)?;
+
+        Ok(StreamSenderRemoved { sender })
+    }
+}
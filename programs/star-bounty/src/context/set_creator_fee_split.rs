@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::events::CreatorFeeSplitUpdated;
+use crate::state::{Creator, CreatorFeeSplit, FeeRecipient, GlobalConfig};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCreatorFeeSplit<'info> {
+    #[account(mut, address = global_config.treasury_manager @ ErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0]
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CreatorFeeSplit::DISCRIMINATOR.len() + CreatorFeeSplit::INIT_SPACE,
+        seeds = [
+            b"creator_fee_split",
+            creator.key().as_ref(),
+        ],
+        bump
+    )]
+    pub creator_fee_split: Account<'info, CreatorFeeSplit>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetCreatorFeeSplit<'info> {
+    /// # Set Creator Fee Split
+    ///
+    /// Sets the team wallets sharing the fees of the creator position, signed by the treasury manager.
+    /// Each recipient gets `bps` of every claim and the treasury keeps the rest. An empty list sends
+    /// everything to the treasury again.
+    pub fn set_creator_fee_split(&mut self, recipients: Vec<FeeRecipient>, bump: [u8; 1]) -> Result<CreatorFeeSplitUpdated> {
+        require!(recipients.len() <= CreatorFeeSplit::MAX_RECIPIENTS, ErrorCode::InvalidFeeSplit);
+
+        let mut total_bps: u16 = 0;
+        for (i, recipient) in recipients.iter().enumerate() {
+            require!(recipient.bps > 0, ErrorCode::InvalidFeeSplit);
+            require_keys_neq!(recipient.wallet, Pubkey::default(), ErrorCode::InvalidFeeSplit);
+            require!(
+                recipients[..i].iter().all(|other| other.wallet != recipient.wallet),
+                ErrorCode::InvalidFeeSplit
+            );
+
+            total_bps = total_bps.checked_add(recipient.bps).ok_or(ErrorCode::InvalidFeeSplit)?;
+        }
+        require!(total_bps <= 10_000, ErrorCode::InvalidFeeSplit);
+
+        self.creator_fee_split.set_inner(CreatorFeeSplit {
+            recipients: recipients.clone(),
+            bump,
+        });
+
+        Ok(CreatorFeeSplitUpdated {
+            launch: self.creator.key(),
+            recipients,
+        })
+    }
+}
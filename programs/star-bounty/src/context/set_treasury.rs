@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::events::TreasuryUpdated;
+use crate::state::{Creator, GlobalConfig, LaunchConfig};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(address = global_config.treasury_manager @ ErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0]
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        mut,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+}
+
+impl<'info> SetTreasury<'info> {
+    /// # Set Treasury
+    ///
+    /// Rotates the owner of the account receiving the creator remainder, signed by the treasury manager.
+    pub fn set_treasury(&mut self, treasury: Pubkey) -> Result<TreasuryUpdated> {
+        require_keys_neq!(treasury, Pubkey::default(), ErrorCode::InvalidTreasury);
+
+        let old_treasury = self.launch_config.treasury;
+        self.launch_config.treasury = treasury;
+
+        Ok(TreasuryUpdated {
+            launch: self.creator.key(),
+            old_treasury,
+            new_treasury: treasury,
+        })
+    }
+}
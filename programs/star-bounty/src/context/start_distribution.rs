@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::context::investor_position::*;
+use crate::events::{DistributionSkipped, FeesClaimed};
+use crate::position::distribution_skipped;
+use crate::state::{DistributionMode, DistributionPhase, DistributionPolicy, DistributionState, FeeLedger, GlobalConfig, LaunchConfig, Rounding};
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartDistribution<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        constraint = global_config.cranker.map_or(true, |cranker| cranker == signer.key()) @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub investor_position: InvestorPosition<'info>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    #[account(
+        constraint = distribution_policy.mode == DistributionMode::Push @ ErrorCode::InvalidDistributionMode,
+        // Pages are paid one at a time, the dust can't go to the largest remainders of the whole epoch
+        constraint = distribution_policy.rounding == Rounding::Floor @ ErrorCode::UnsupportedRounding,
+        seeds = [
+            b"distribution_policy",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        mut,
+        constraint = distribution_state.phase == DistributionPhase::Idle @ ErrorCode::DistributionInProgress,
+        seeds = [
+            b"distribution",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Account<'info, DistributionState>,
+    #[account(
+        mut,
+        seeds = [
+            b"fee_ledger",
+            investor_position.creator.key().as_ref(),
+        ],
+        bump = fee_ledger.bump[0],
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+}
+
+impl<'info> StartDistribution<'info> {
+    pub fn claim(&mut self) -> Result<FeesClaimed> {
+        self.investor_position.claim(&mut self.distribution_state, &mut self.fee_ledger)
+    }
+
+    /// # Start Distribution
+    ///
+    /// Opens a distribution epoch once the cooldown has passed: freezes the new token B
+    /// fees, the stream count and the timestamp locked amounts are computed at. Pages are
+    /// then tallied and paid with `distribute_page`, and `finalize_distribution` closes the epoch.
+    pub fn start_distribution(&mut self) -> Result<Option<DistributionSkipped>> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // Fails within the cooldown, the fees wait for the next distribution if there's only dust
+        if let Some(skipped) = distribution_skipped(
+            &mut self.investor_position.creator,
+            &self.distribution_policy,
+            &self.distribution_state,
+            now,
+        )? {
+            return Ok(Some(skipped));
+        }
+
+        let fees = self.distribution_state.pending_fees;
+
+        let state = &mut self.distribution_state;
+
+        state.streams = self.investor_position.creator.streams;
+        require!(state.pages() <= DistributionState::MAX_PAGES, ErrorCode::TooManyStreams);
+
+        state.epoch = state.epoch.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        state.started_at = now;
+        state.claimed = fees;
+        state.pending_fees = 0;
+        // Tallying removes the unvested remainder of the cancelled streams
+        state.initial_locked = self.launch_config.initial_locked(0)?;
+        state.total_locked = 0;
+        state.owed = 0;
+        state.distributable = 0;
+        state.settled = 0;
+        state.distributed = 0;
+        state.transfer_fees = 0;
+        state.tallied_pages = [0; 64];
+        state.paid_pages = [0; 64];
+
+        // Nothing to tally without streams, everything goes to the treasury
+        state.phase = if state.streams == 0 {
+            DistributionPhase::Paying
+        } else {
+            DistributionPhase::Tallying
+        };
+
+        self.fee_ledger.record_epoch(now)?;
+
+        Ok(None)
+    }
+}
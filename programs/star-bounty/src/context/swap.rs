@@ -1,16 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{Mint, Token, TokenAccount}
+    token_interface::{Mint, TokenAccount, TokenInterface}
 };
 use cp_amm::{
     const_pda::pool_authority::ID as POOL_AUTHORITY_ID, constants::seeds::{CUSTOMIZABLE_POOL_PREFIX, TOKEN_VAULT_PREFIX }, instructions::initialize_pool::{max_key, min_key}, 
 };
 
+use crate::state::{Creator, GlobalConfig, LaunchConfig};
+use crate::error::ErrorCode;
+
 #[derive(Accounts)]
 pub struct Swap<'info> {
     // DAMM V2 Accounts
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
     #[account(
         address = POOL_AUTHORITY_ID
     )]
@@ -19,7 +45,7 @@ pub struct Swap<'info> {
     #[account(
         mut,
         seeds = [
-            CUSTOMIZABLE_POOL_PREFIX.as_ref(),
+            CUSTOMIZABLE_POOL_PREFIX,
             &max_key(&mint_a.key(), &mint_b.key()),
             &min_key(&mint_a.key(), &mint_b.key()),
         ],
@@ -28,12 +54,14 @@ pub struct Swap<'info> {
     )]
     /// CHECK: Account checked and initialized by DAMMV2
     pub pool: UncheckedAccount<'info>,
-    pub mint_a: Box<Account<'info, Mint>>,
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_a_program)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = token_b_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
         seeds = [
-            TOKEN_VAULT_PREFIX.as_ref(),
+            TOKEN_VAULT_PREFIX,
             mint_a.key().as_ref(),
             pool.key().as_ref(),
         ],
@@ -45,7 +73,7 @@ pub struct Swap<'info> {
     #[account(
         mut,
         seeds = [
-            TOKEN_VAULT_PREFIX.as_ref(),
+            TOKEN_VAULT_PREFIX,
             mint_b.key().as_ref(),
             pool.key().as_ref(),
         ],
@@ -59,20 +87,21 @@ pub struct Swap<'info> {
         mut,
         token::authority = payer,
     )]
-    pub input_token_account: Box<Account<'info, TokenAccount>>,
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         token::authority = payer,
     )]
-    pub output_token_account: Box<Account<'info, TokenAccount>>,
-    pub token_program: Program<'info, Token>,
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_a_program: Interface<'info, TokenInterface>,
+    pub token_b_program: Interface<'info, TokenInterface>,
     #[account(
         seeds = [b"__event_authority"], 
         seeds::program = cp_amm::ID,
         bump
     )]
     /// CHECK: Account checked and initialized by DAMMV2
-    pub event_authority: AccountInfo<'info>,
+    pub dammv2_event_authority: AccountInfo<'info>,
     #[account(address = cp_amm::ID)]
     /// CHECK: Self-CPI will fail if the program is not the current program
     pub dammv2_program: AccountInfo<'info>,
@@ -91,10 +120,10 @@ impl<'info> Swap<'info> {
             token_a_mint: self.mint_a.to_account_info(),
             token_b_mint: self.mint_b.to_account_info(),
             payer: self.payer.to_account_info(),
-            token_a_program: self.token_program.to_account_info(),
-            token_b_program: self.token_program.to_account_info(),
+            token_a_program: self.token_a_program.to_account_info(),
+            token_b_program: self.token_b_program.to_account_info(),
             referral_token_account: None,
-            event_authority: self.event_authority.to_account_info(),
+            event_authority: self.dammv2_event_authority.to_account_info(),
             program: self.dammv2_program.to_account_info(),
             
         };
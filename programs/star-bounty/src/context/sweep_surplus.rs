@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::events::SurplusSwept;
+use crate::state::{Creator, DistributionPhase, DistributionState, FeeIndex, GlobalConfig, LaunchConfig};
+use crate::token::transfer_signed;
+use crate::error::ErrorCode;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    #[account(address = global_config.treasury_manager @ ErrorCode::Unauthorized)]
+    pub authority: Signer<'info>,
+    #[account(
+        constraint = !global_config.paused @ ErrorCode::Paused,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mint::token_program = token_program)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint_b.key().as_ref(),
+        ],
+        bump = creator.bump[0],
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        constraint = !launch_config.paused @ ErrorCode::Paused,
+        seeds = [
+            b"launch_config",
+            creator.key().as_ref(),
+        ],
+        bump = launch_config.bump[0],
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+    // An open epoch still holds its frozen fees in the account
+    #[account(
+        constraint = distribution_state.phase == DistributionPhase::Idle @ ErrorCode::DistributionInProgress,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Box<Account<'info, DistributionState>>,
+    #[account(
+        seeds = [
+            b"fee_index",
+            creator.key().as_ref(),
+        ],
+        bump = fee_index.bump[0],
+    )]
+    pub fee_index: Box<Account<'info, FeeIndex>>,
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == mint_b.key() @ ErrorCode::InvalidTreasury,
+        constraint = creator_token_account.owner == launch_config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [
+            b"investor_fee_pos_owner",
+            mint_b.key().as_ref()
+        ],
+        bump = creator.position_owner_bump[0],
+    )]
+    /// CHECK: Data-less PDA, only used to sign for the investor position
+    pub investor_fee_pos_owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::token_program = token_program,
+        associated_token::authority = investor_fee_pos_owner,
+    )]
+    pub token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SweepSurplus<'info> {
+    /// # Sweep Surplus
+    ///
+    /// Sends the token B the distributions don't account for to the treasury: donations to the
+    /// position owner account and tokens left over from the investor deposit. Pending fees, the
+    /// investor reserve, unclaimed pull fees and their transfer fee budget stay in the account.
+    pub fn sweep_surplus(&mut self) -> Result<SurplusSwept> {
+        let accounted = self.distribution_state.pending_fees
+            .checked_add(self.distribution_state.investor_reserve)
+            .and_then(|x| x.checked_add(self.fee_index.unclaimed))
+            .and_then(|x| x.checked_add(self.fee_index.transfer_fee_budget))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let surplus = self.token_b_account.amount.saturating_sub(accounted);
+        require_gt!(surplus, 0, ErrorCode::NoSurplus);
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"investor_fee_pos_owner".as_ref(),
+            self.mint_b.to_account_info().key.as_ref(),
+            &self.creator.position_owner_bump
+        ]];
+
+        transfer_signed(
+            &self.token_program,
+            &self.mint_b,
+            self.token_b_account.to_account_info(),
+            self.creator_token_account.to_account_info(),
+            self.investor_fee_pos_owner.to_account_info(),
+            &signer_seeds,
+            surplus,
+        )?;
+
+        Ok(SurplusSwept {
+            launch: self.creator.key(),
+            epoch: self.distribution_state.epoch,
+            treasury: self.creator_token_account.key(),
+            amount: surplus,
+        })
+    }
+}
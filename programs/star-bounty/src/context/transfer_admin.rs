@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::state::GlobalConfig;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    /// # Propose Admin
+    ///
+    /// First step of the transfer, the new admin has to accept before it takes effect.
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.global_config.pending_admin = Some(new_admin);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = global_config.pending_admin == Some(pending_admin.key()) @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    /// # Accept Admin
+    ///
+    /// Second step of the transfer, signed by the proposed admin.
+    pub fn accept_admin(&mut self) -> Result<()> {
+        self.global_config.admin = self.pending_admin.key();
+        self.global_config.pending_admin = None;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::state::{Creator, DistributionMode, DistributionPhase, DistributionPolicy, DistributionState, GlobalConfig, Rounding};
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DistributionPolicyArgs {
+    /// Balance under which a claim skips the distribution
+    pub dust_threshold: u64,
+    /// Maximum share of the claimed fees that goes to investors, in bps
+    pub max_investor_share_bps: u16,
+    /// Maximum amount distributed to investors in a single claim
+    pub daily_cap: u64,
+    /// Seconds between two claims
+    pub cooldown: i64,
+    /// Push or pull distribution, pull can't be left once selected
+    pub mode: DistributionMode,
+    /// Keep the capped excess and rounding dust of new fees for later distributions, a reserve
+    /// already carried stays owed either way
+    pub carry_over: bool,
+    /// Rounding of the pro-rata shares in `claim_fees`, paginated and pull distributions only
+    /// support `Floor`
+    pub rounding: Rounding,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDistributionPolicy<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"global_config"],
+        bump = global_config.bump[0],
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [
+            b"creator",
+            mint.key().as_ref(),
+        ],
+        bump = creator.bump[0]
+    )]
+    pub creator: Account<'info, Creator>,
+    #[account(
+        mut,
+        seeds = [
+            b"distribution_policy",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_policy.bump[0],
+    )]
+    pub distribution_policy: Account<'info, DistributionPolicy>,
+    #[account(
+        // An open epoch fixed its amounts under the current policy
+        constraint = distribution_state.phase == DistributionPhase::Idle @ ErrorCode::DistributionInProgress,
+        seeds = [
+            b"distribution",
+            creator.key().as_ref(),
+        ],
+        bump = distribution_state.bump[0],
+    )]
+    pub distribution_state: Account<'info, DistributionState>,
+}
+
+impl<'info> UpdateDistributionPolicy<'info> {
+    pub fn update_distribution_policy(&mut self, args: DistributionPolicyArgs) -> Result<()> {
+        require!(
+            args.max_investor_share_bps <= 10_000,
+            ErrorCode::InvalidInvestorShare
+        );
+        require!(args.daily_cap > 0, ErrorCode::InvalidDailyCap);
+        require!(args.cooldown > 0, ErrorCode::InvalidCooldown);
+        // Checkpoints only exist in pull mode, going back would strand unclaimed fees
+        require!(
+            self.distribution_policy.mode == DistributionMode::Push || args.mode == DistributionMode::Pull,
+            ErrorCode::DistributionModeLocked
+        );
+        // Pull claims are paid one stream at a time and can't rank the remainders
+        require!(
+            args.rounding == Rounding::Floor || args.mode == DistributionMode::Push,
+            ErrorCode::UnsupportedRounding
+        );
+
+        self.distribution_policy.dust_threshold = args.dust_threshold;
+        self.distribution_policy.max_investor_share_bps = args.max_investor_share_bps;
+        self.distribution_policy.daily_cap = args.daily_cap;
+        self.distribution_policy.cooldown = args.cooldown;
+        self.distribution_policy.mode = args.mode;
+        self.distribution_policy.carry_over = args.carry_over;
+        self.distribution_policy.rounding = args.rounding;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+use crate::math::pro_rata;
+use crate::transfer_fee::SharePayout;
+use crate::state::{locked_amount, Creator, DistributionPolicy, GlobalConfig, LaunchConfig, StreamStatus};
+
+/// Locked amounts of the streams of a launch at one point in time
+pub struct StreamTally {
+    // Y0
+    pub initial_locked: u64,
+    pub total_locked: u64,
+    pub locked: Vec<u64>,
+    pub recipients: Vec<Pubkey>,
+}
+
+impl StreamTally {
+    /// Reads the streams of `creator` from the `[metadata, recipient_tokens]` pairs at the start
+    /// of `accounts`. Ended or cancelled streams get nothing, Y0 is the launch deposits less what
+    /// the cancelled streams returned.
+    pub fn load(global_config: &GlobalConfig, creator: &Account<Creator>, launch_config: &LaunchConfig, mint: &Pubkey, accounts: &[AccountInfo], now: i64) -> Result<Self> {
+        let streams = creator.streams as usize;
+
+        let mut tally = StreamTally {
+            initial_locked: 0,
+            total_locked: 0,
+            locked: Vec::with_capacity(streams),
+            recipients: Vec::with_capacity(streams),
+        };
+        let mut cancelled: u64 = 0;
+
+        for i in 0..streams {
+            let metadata_account = &accounts[i * 2];
+            let token_account = &accounts[i * 2 + 1];
+
+            require_eq!(
+                creator.stream_metadata(&creator.key(), i as u32),
+                metadata_account.key()
+            );
+
+            let stream = match launch_config.load_stream_status(global_config, metadata_account, mint, creator.legacy_stream(i as u32))? {
+                StreamStatus::Live(stream) => stream,
+                status => {
+                    if let StreamStatus::Cancelled { unvested } = status {
+                        cancelled = cancelled.checked_add(unvested).ok_or(ProgramError::ArithmeticOverflow)?;
+                    }
+                    tally.locked.push(0);
+                    tally.recipients.push(Pubkey::default());
+                    continue;
+                }
+            };
+            require_eq!(stream.recipient_tokens, token_account.key());
+
+            let locked = locked_amount(&stream, now as u64);
+
+            tally.total_locked = tally.total_locked.checked_add(locked).ok_or(ProgramError::ArithmeticOverflow)?;
+            tally.locked.push(locked);
+            tally.recipients.push(stream.recipient);
+        }
+
+        tally.initial_locked = launch_config.initial_locked(cancelled)?;
+
+        Ok(tally)
+    }
+}
+
+/// Split of a push distribution between the streams, the investor reserve and the treasury
+pub struct EpochPayout {
+    pub owed: u64,
+    pub distributable: u64,
+    pub payouts: Vec<SharePayout>,
+    // Sum of what the streams received
+    pub distributed: u64,
+    pub transfer_fees: u64,
+    pub investor_reserve: u64,
+    pub treasury_amount: u64,
+}
+
+impl EpochPayout {
+    /// Splits `fees` plus the investor `reserve` pro-rata to the locked amounts of `tally`. The
+    /// transfer fees on the shares come out of the treasury part, as far as it goes. Past that,
+    /// the streams receive their shares less the fee.
+    pub fn compute(policy: &DistributionPolicy, mint: &AccountInfo, fees: u64, reserve: u64, tally: &StreamTally) -> Result<Self> {
+        let quote = policy.investor_quote(fees, tally.total_locked, tally.initial_locked)?;
+        let owed = policy.investor_owed(quote, reserve)?;
+        let distributable = policy.investor_distributable(owed);
+
+        // weight_i = locked_i / total_locked
+        let shares = pro_rata(distributable, &tally.locked, policy.rounding)?;
+
+        let mut fee_budget = fees
+            .checked_add(reserve)
+            .and_then(|x| x.checked_sub(owed))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut payouts: Vec<SharePayout> = Vec::with_capacity(shares.len());
+        let mut settled: u64 = 0;
+        let mut distributed: u64 = 0;
+        let mut transfer_fees: u64 = 0;
+
+        for &share in shares.iter() {
+            if share == 0 {
+                payouts.push(SharePayout::default());
+                continue;
+            }
+
+            let payout = SharePayout::new(mint, share, fee_budget)?;
+            fee_budget -= payout.fee_paid();
+
+            settled = settled.checked_add(share).ok_or(ProgramError::ArithmeticOverflow)?;
+            distributed = distributed.checked_add(payout.received).ok_or(ProgramError::ArithmeticOverflow)?;
+            transfer_fees = transfer_fees.checked_add(payout.withheld()).ok_or(ProgramError::ArithmeticOverflow)?;
+            payouts.push(payout);
+        }
+
+        // Keep what's still owed to investors, the remainder goes to the creator
+        let investor_reserve = policy.investor_reserve(owed, settled, reserve, tally.total_locked);
+        let treasury_amount = fees
+            .checked_add(reserve)
+            .and_then(|x| x.checked_sub(distributed))
+            .and_then(|x| x.checked_sub(investor_reserve))
+            .and_then(|x| x.checked_sub(transfer_fees))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(EpochPayout {
+            owed,
+            distributable,
+            payouts,
+            distributed,
+            transfer_fees,
+            investor_reserve,
+            treasury_amount,
+        })
+    }
+}
@@ -8,4 +8,90 @@ pub enum ErrorCode {
     InvalidCollectFeeMode,
     #[msg("No vested amount available")]
     NoVestedAmount,
+    #[msg("Invalid commitment bps")]
+    InvalidCommitment,
+    #[msg("Decimals don't match the mint")]
+    InvalidDecimals,
+    #[msg("Invalid pool token amount")]
+    InvalidPoolAmount,
+    #[msg("Investor share can't exceed 10,000 bps")]
+    InvalidInvestorShare,
+    #[msg("Invalid daily cap")]
+    InvalidDailyCap,
+    #[msg("Invalid cooldown")]
+    InvalidCooldown,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid role account")]
+    InvalidRoleAccount,
+    #[msg("Role is not assigned")]
+    RoleNotAssigned,
+    #[msg("Paused")]
+    Paused,
+    #[msg("Not paused")]
+    NotPaused,
+    #[msg("Creator is already migrated")]
+    AlreadyMigrated,
+    #[msg("Invalid period")]
+    InvalidPeriod,
+    #[msg("Invalid cliff")]
+    InvalidCliff,
+    #[msg("Start time is in the past")]
+    InvalidStartTime,
+    #[msg("Stream metadata isn't owned by Streamflow")]
+    InvalidStreamOwner,
+    #[msg("Stream mint doesn't match the launch")]
+    InvalidStreamMint,
+    #[msg("Stream sender isn't trusted")]
+    InvalidStreamSender,
+    #[msg("Stream allows top-ups")]
+    StreamTopupEnabled,
+    #[msg("Stream cancel/transfer permissions aren't allowed by the launch")]
+    InvalidStreamPermissions,
+    #[msg("Invalid treasury")]
+    InvalidTreasury,
+    #[msg("A distribution epoch is in progress")]
+    DistributionInProgress,
+    #[msg("No distribution epoch in progress")]
+    NoDistributionInProgress,
+    #[msg("Invalid page range")]
+    InvalidPage,
+    #[msg("Page already processed")]
+    PageAlreadyProcessed,
+    #[msg("Pages left to process")]
+    PagesPending,
+    #[msg("Too many streams for a distribution epoch")]
+    TooManyStreams,
+    #[msg("Instruction not available in the current distribution mode")]
+    InvalidDistributionMode,
+    #[msg("Pull distribution can't be switched back to push")]
+    DistributionModeLocked,
+    #[msg("No surplus to sweep")]
+    NoSurplus,
+    #[msg("Stream ledger doesn't match the stream")]
+    InvalidStreamLedger,
+    #[msg("Unexpected number of remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Price range is invalid or leaves a side of the deposit unused")]
+    InvalidPriceRange,
+    #[msg("Pool fees are outside of the DAMM v2 limits")]
+    InvalidPoolFees,
+    #[msg("Activation point must be ahead, and set with an alpha vault")]
+    InvalidActivation,
+    #[msg("Fee split has too many recipients, a duplicate or default wallet, a zero share or shares above 100%")]
+    InvalidFeeSplit,
+    #[msg("Largest remainder rounding is only supported by claim_fees")]
+    UnsupportedRounding,
+    #[msg("Too many stream senders, remove one first")]
+    TooManyStreamSenders,
+    #[msg("The current stream manager can't be removed from the stream senders")]
+    StreamSenderInUse,
+    #[msg("Not a stream sender")]
+    StreamSenderNotFound,
+    #[msg("v1 streams left to load with load_legacy_streams")]
+    LegacyStreamsPending,
+    #[msg("Cooldown hasn't passed since the last claim")]
+    CooldownActive,
+    #[msg("Every stream needs a registered checkpoint")]
+    StreamNotRegistered,
 }
\ No newline at end of file
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FeeRecipient, Role};
+
+/// Events of a distribution crank, in the order the instruction handler emits them with `emit_cpi!`
+#[derive(Default)]
+pub struct DistributionEvents {
+    pub skipped: Option<DistributionSkipped>,
+    pub payouts: Vec<InvestorPayout>,
+    pub creator_remainder: Option<CreatorRemainder>,
+    pub distributed: Option<FeesDistributed>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SkipReason {
+    Dust,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct StreamSenderAdded {
+    pub sender: Pubkey,
+}
+
+#[event]
+pub struct StreamSenderRemoved {
+    pub sender: Pubkey,
+}
+
+#[event]
+pub struct Paused {
+    /// None when the whole program is paused
+    pub launch: Option<Pubkey>,
+    pub reason: u16,
+}
+
+#[event]
+pub struct Unpaused {
+    pub launch: Option<Pubkey>,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub launch: Pubkey,
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+}
+
+#[event]
+pub struct CreatorFeeSplitUpdated {
+    pub launch: Pubkey,
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[event]
+pub struct CreatorPositionFeesClaimed {
+    pub launch: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub treasury: Pubkey,
+    /// Part of the fees left to the treasury once the team wallets are paid
+    pub treasury_amount_a: u64,
+    pub treasury_amount_b: u64,
+}
+
+#[event]
+pub struct TeamFeePayout {
+    pub launch: Pubkey,
+    pub wallet: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+// Every launch event carries the distribution epoch: the last distribution run, or for
+// the fee events the distribution the fees go to.
+
+#[event]
+pub struct PoolInitialized {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub pool: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[event]
+pub struct StreamCreated {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub stream_index: u32,
+    pub metadata: Pubkey,
+    pub recipient: Pubkey,
+    pub net_amount: u64,
+    pub start_time: u64,
+}
+
+#[event]
+pub struct InvestorPositionDeposited {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub position: Pubkey,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct DistributionSkipped {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub reason: SkipReason,
+}
+
+#[event]
+pub struct InvestorPayout {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub stream_index: u32,
+    pub recipient: Pubkey,
+    /// Locked amount the share is computed on
+    pub locked: u64,
+    pub share: u64,
+}
+
+#[event]
+pub struct CreatorRemainder {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SurplusSwept {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DistributionAborted {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    /// Received by the streams before the abort
+    pub distributed: u64,
+    pub investor_reserve: u64,
+    /// New fees back to wait for the next distribution, the refunded ones included
+    pub pending_fees: u64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub launch: Pubkey,
+    pub epoch: u64,
+    /// New fees split in this distribution
+    pub fees: u64,
+    /// Paid to investors, or accrued to the fee index in pull mode
+    pub distributed: u64,
+    pub treasury_amount: u64,
+    /// Still owed to investors after this distribution
+    pub investor_reserve: u64,
+}
@@ -6,38 +6,265 @@ mod context;
 use context::*;
 mod state;
 mod error;
+mod events;
+mod math;
+mod transfer_fee;
+mod token;
+mod position;
+mod distribution;
 
-pub const ADMIN: Pubkey = pubkey!("1oksyAnDKAFv4qgDDrwDh2XDHNM4vhXziXo5Qb5Rnmc");
+use state::{FeeRecipient, Role};
 
-#[program]
-pub mod star_bounty {
+/// Emits the events of a distribution crank in order. `emit_cpi!` reads the event authority from
+/// `ctx`, so it's bound here for the expansion.
+macro_rules! emit_distribution_events {
+    ($ctx:expr, $events:expr) => {{
+        let ctx = &$ctx;
+        let events: events::DistributionEvents = $events;
+
+        if let Some(event) = events.skipped {
+            emit_cpi!(event);
+        }
+        for event in events.payouts {
+            emit_cpi!(event);
+        }
+        if let Some(event) = events.creator_remainder {
+            emit_cpi!(event);
+        }
+        if let Some(event) = events.distributed {
+            emit_cpi!(event);
+        }
+    }};
+}
+
+// `#[program]` emits its IDL handlers next to the program module, they call the deprecated
+// `AccountInfo::realloc`
+#[allow(deprecated)]
+mod handlers {
     use super::*;
 
-    
-    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
-        ctx.accounts.populate_creator([ctx.bumps.creator])?;
-        ctx.accounts.delegate_token_account([ctx.bumps.creator])?;
-        ctx.accounts.create_pool([ctx.bumps.position_nft_mint])?;
-        ctx.accounts.revoke_token_account([ctx.bumps.creator])
-    }
+    #[program]
+    pub mod star_bounty {
+        use super::*;
 
-    pub fn create_stream(ctx: Context<CreateStream>) -> Result<()> {
-        ctx.accounts.create_stream([ctx.bumps.metadata])
-    }
+        pub fn initialize_global_config(ctx: Context<InitializeGlobalConfig>, admin: Pubkey) -> Result<()> {
+            ctx.accounts.initialize_global_config(admin, [ctx.bumps.global_config])
+        }
 
-    pub fn swap(ctx: Context<Swap>, amount: u64) -> Result<()> {
-        ctx.accounts.swap_tokens(amount)
-    }
+        pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+            ctx.accounts.propose_admin(new_admin)
+        }
 
-    pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
-        ctx.accounts.initialize_investor_fee_pos_owner([ctx.bumps.investor_fee_pos_owner])?;
-        ctx.accounts.deposit([ctx.bumps.position_nft_mint])
-    }
-    
-    pub fn claim_fees<'info>(ctx: Context<'_, '_, '_, 'info, ClaimFees<'info>>) -> Result<()> {
-        ctx.accounts.claim()?;
-        ctx.accounts.distribute(ctx.remaining_accounts)
-    }
+        pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+            ctx.accounts.accept_admin()
+        }
+
+        pub fn grant_role(ctx: Context<ManageRole>, role: Role, account: Pubkey) -> Result<()> {
+            let (sender_added, granted) = ctx.accounts.grant_role(role, account)?;
+            if let Some(event) = sender_added {
+                emit_cpi!(event);
+            }
+            emit_cpi!(granted);
+            Ok(())
+        }
+
+        pub fn revoke_role(ctx: Context<ManageRole>, role: Role) -> Result<()> {
+            let event = ctx.accounts.revoke_role(role)?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn add_stream_sender(ctx: Context<ManageRole>, sender: Pubkey) -> Result<()> {
+            if let Some(event) = ctx.accounts.add_stream_sender(sender)? {
+                emit_cpi!(event);
+            }
+            Ok(())
+        }
+
+        pub fn remove_stream_sender(ctx: Context<ManageRole>, sender: Pubkey) -> Result<()> {
+            let event = ctx.accounts.remove_stream_sender(sender)?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn pause(ctx: Context<GlobalPause>, reason: u16) -> Result<()> {
+            let event = ctx.accounts.pause(reason)?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn unpause(ctx: Context<GlobalPause>) -> Result<()> {
+            let event = ctx.accounts.unpause()?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn pause_launch(ctx: Context<LaunchPause>, reason: u16) -> Result<()> {
+            let event = ctx.accounts.pause_launch(reason)?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn unpause_launch(ctx: Context<LaunchPause>) -> Result<()> {
+            let event = ctx.accounts.unpause_launch()?;
+            emit_cpi!(event);
+            Ok(())
+        }
+        
+        pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+            let event = ctx.accounts.set_treasury(treasury)?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn set_creator_fee_split(ctx: Context<SetCreatorFeeSplit>, recipients: Vec<FeeRecipient>) -> Result<()> {
+            let event = ctx.accounts.set_creator_fee_split(recipients, [ctx.bumps.creator_fee_split])?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn initialize_pool(ctx: Context<InitializePool>, args: LaunchConfigArgs) -> Result<()> {
+            ctx.accounts.populate_creator([ctx.bumps.creator])?;
+            ctx.accounts.populate_launch_config(&args, [ctx.bumps.launch_config])?;
+            ctx.accounts.populate_distribution_accounts(DistributionBumps {
+                distribution_policy: ctx.bumps.distribution_policy,
+                distribution_state: ctx.bumps.distribution_state,
+                fee_index: ctx.bumps.fee_index,
+                fee_ledger: ctx.bumps.fee_ledger,
+            })?;
+            ctx.accounts.delegate_token_account([ctx.bumps.creator])?;
+            let event = ctx.accounts.create_pool(&args, [ctx.bumps.position_nft_mint])?;
+            ctx.accounts.revoke_token_account([ctx.bumps.creator])?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn create_stream(ctx: Context<CreateStream>, args: CreateStreamArgs) -> Result<()> {
+            let event = ctx.accounts.create_stream(args, [ctx.bumps.metadata])?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn create_streams_batch<'info>(ctx: Context<'_, '_, '_, 'info, CreateStreamsBatch<'info>>, allocations: Vec<CreateStreamArgs>) -> Result<()> {
+            for event in ctx.accounts.create_streams_batch(allocations, ctx.remaining_accounts)? {
+                emit_cpi!(event);
+            }
+            Ok(())
+        }
 
+        pub fn swap(ctx: Context<Swap>, amount: u64) -> Result<()> {
+            ctx.accounts.swap_tokens(amount)
+        }
 
-}
\ No newline at end of file
+        pub fn deposit(ctx: Context<Deposit>) -> Result<()> {
+            ctx.accounts.initialize_investor_fee_pos_owner([ctx.bumps.investor_fee_pos_owner])?;
+            let event = ctx.accounts.deposit([ctx.bumps.position_nft_mint])?;
+            emit_cpi!(event);
+            Ok(())
+        }
+        
+        pub fn claim_fees<'info>(ctx: Context<'_, '_, '_, 'info, ClaimFees<'info>>) -> Result<()> {
+            let claimed = ctx.accounts.claim()?;
+            let events = ctx.accounts.distribute(ctx.remaining_accounts)?;
+            emit_cpi!(claimed);
+            emit_distribution_events!(ctx, events);
+            Ok(())
+        }
+
+        pub fn preview_distribution<'info>(ctx: Context<'_, '_, '_, 'info, PreviewDistribution<'info>>) -> Result<DistributionPreview> {
+            ctx.accounts.preview_distribution(ctx.remaining_accounts)
+        }
+
+        pub fn start_distribution(ctx: Context<StartDistribution>) -> Result<()> {
+            let claimed = ctx.accounts.claim()?;
+            let skipped = ctx.accounts.start_distribution()?;
+            emit_cpi!(claimed);
+            if let Some(event) = skipped {
+                emit_cpi!(event);
+            }
+            Ok(())
+        }
+
+        pub fn distribute_page<'info>(ctx: Context<'_, '_, '_, 'info, DistributePage<'info>>, start_index: u32, count: u32) -> Result<()> {
+            for event in ctx.accounts.distribute_page(start_index, count, ctx.remaining_accounts)? {
+                emit_cpi!(event);
+            }
+            Ok(())
+        }
+
+        pub fn finalize_distribution(ctx: Context<FinalizeDistribution>) -> Result<()> {
+            let events = ctx.accounts.finalize_distribution()?;
+            emit_distribution_events!(ctx, events);
+            Ok(())
+        }
+
+        pub fn abort_distribution(ctx: Context<AbortDistribution>) -> Result<()> {
+            let event = ctx.accounts.abort_distribution()?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn register_stream_checkpoint(ctx: Context<RegisterStreamCheckpoint>, index: u32) -> Result<()> {
+            ctx.accounts.register_stream_checkpoint(index, [ctx.bumps.stream_checkpoint])
+        }
+
+        pub fn accrue_fees(ctx: Context<AccrueFees>) -> Result<()> {
+            let claimed = ctx.accounts.claim()?;
+            let events = ctx.accounts.accrue_fees()?;
+            emit_cpi!(claimed);
+            emit_distribution_events!(ctx, events);
+            Ok(())
+        }
+
+        pub fn initialize_stream_ledger(ctx: Context<InitializeStreamLedger>, index: u32) -> Result<()> {
+            ctx.accounts.initialize_stream_ledger(index, [ctx.bumps.stream_ledger])
+        }
+
+        pub fn claim_investor_fees(ctx: Context<ClaimInvestorFees>, index: u32) -> Result<()> {
+            if let Some(event) = ctx.accounts.claim_investor_fees(index)? {
+                emit_cpi!(event);
+            }
+            Ok(())
+        }
+
+        pub fn sweep_surplus(ctx: Context<SweepSurplus>) -> Result<()> {
+            let event = ctx.accounts.sweep_surplus()?;
+            emit_cpi!(event);
+            Ok(())
+        }
+
+        pub fn claim_creator_position_fees<'info>(ctx: Context<'_, '_, '_, 'info, ClaimCreatorPositionFees<'info>>) -> Result<()> {
+            let (payouts, claimed) = ctx.accounts.claim_creator_position_fees(ctx.remaining_accounts)?;
+            for event in payouts {
+                emit_cpi!(event);
+            }
+            emit_cpi!(claimed);
+            Ok(())
+        }
+
+        pub fn migrate_creator(ctx: Context<MigrateCreator>) -> Result<()> {
+            ctx.accounts.migrate_creator()
+        }
+
+        pub fn initialize_launch_accounts(ctx: Context<InitializeLaunchAccounts>, args: LaunchAccountsArgs) -> Result<()> {
+            ctx.accounts.populate_launch_config(&args, [ctx.bumps.launch_config])?;
+            ctx.accounts.populate_distribution_accounts(DistributionBumps {
+                distribution_policy: ctx.bumps.distribution_policy,
+                distribution_state: ctx.bumps.distribution_state,
+                fee_index: ctx.bumps.fee_index,
+                fee_ledger: ctx.bumps.fee_ledger,
+            })
+        }
+
+        pub fn load_legacy_streams<'info>(ctx: Context<'_, '_, '_, 'info, LoadLegacyStreams<'info>>) -> Result<()> {
+            ctx.accounts.load_legacy_streams(ctx.remaining_accounts)
+        }
+
+        pub fn update_distribution_policy(ctx: Context<UpdateDistributionPolicy>, args: DistributionPolicyArgs) -> Result<()> {
+            ctx.accounts.update_distribution_policy(args)
+        }
+
+
+    }
+}
+pub use handlers::*;
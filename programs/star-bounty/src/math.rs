@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Rounding;
+
+/// Splits `amount` pro-rata to `weights`.
+///
+/// `Floor` rounds every share down and leaves the dust unallocated. `LargestRemainder`
+/// hands the dust out one unit at a time to the largest remainders, ties going to the
+/// lowest index, so the shares add up to exactly `amount` as long as a weight is non-zero.
+pub fn pro_rata(amount: u64, weights: &[u64], rounding: Rounding) -> Result<Vec<u64>> {
+    let total: u128 = weights.iter().map(|&weight| weight as u128).sum();
+
+    if total == 0 {
+        return Ok(vec![0; weights.len()]);
+    }
+
+    let mut shares = Vec::with_capacity(weights.len());
+    let mut remainders = Vec::with_capacity(weights.len());
+    let mut allocated: u64 = 0;
+
+    for (i, &weight) in weights.iter().enumerate() {
+        // amount * weight fits in a u128, the share is at most `amount`
+        let scaled = amount as u128 * weight as u128;
+        let share = (scaled / total) as u64;
+
+        shares.push(share);
+        remainders.push((scaled % total, i));
+        allocated += share;
+    }
+
+    if rounding == Rounding::LargestRemainder {
+        // Each share lost less than one unit, so the dust is smaller than the number of weights
+        let dust = (amount - allocated) as usize;
+
+        remainders.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        for &(_, i) in remainders.iter().take(dust) {
+            shares[i] += 1;
+        }
+    }
+
+    Ok(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocate(amount: u64, weights: &[u64]) -> Vec<u64> {
+        pro_rata(amount, weights, Rounding::LargestRemainder).unwrap()
+    }
+
+    #[test]
+    fn floor_leaves_dust() {
+        let shares = pro_rata(10, &[1, 1, 1], Rounding::Floor).unwrap();
+        assert_eq!(shares, vec![3, 3, 3]);
+    }
+
+    #[test]
+    fn equal_weights_break_ties_by_index() {
+        assert_eq!(allocate(10, &[1, 1, 1]), vec![4, 3, 3]);
+        assert_eq!(allocate(11, &[5, 5, 5]), vec![4, 4, 3]);
+    }
+
+    #[test]
+    fn largest_remainders_get_the_dust() {
+        // Exact shares 1.4, 2.1, 3.5: the last one gets the unit
+        assert_eq!(allocate(7, &[4, 6, 10]), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn zero_weights_get_nothing() {
+        assert_eq!(allocate(5, &[0, 3, 0, 3, 0]), vec![0, 3, 0, 2, 0]);
+        assert_eq!(allocate(5, &[0, 0, 0]), vec![0, 0, 0]);
+        assert_eq!(allocate(5, &[]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn zero_amount() {
+        assert_eq!(allocate(0, &[1, 2, 3]), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn one_whale_many_minnows() {
+        let mut weights = vec![1u64; 999];
+        weights.push(u64::MAX / 2);
+
+        let shares = allocate(1_000_000, &weights);
+
+        assert_eq!(shares.iter().sum::<u64>(), 1_000_000);
+        assert!(shares[999] >= 999_999);
+    }
+
+    #[test]
+    fn more_weights_than_units() {
+        let weights = vec![7u64; 1_000];
+        let shares = allocate(999, &weights);
+
+        assert_eq!(shares.iter().sum::<u64>(), 999);
+        assert!(shares[..999].iter().all(|&share| share == 1));
+        assert_eq!(shares[999], 0);
+    }
+
+    #[test]
+    fn extreme_values_sum_exactly() {
+        let weights = [u64::MAX, u64::MAX - 1, 1, 3, u64::MAX / 3];
+
+        for amount in [1, 2, 3, 1_000_000_007, u64::MAX / 2, u64::MAX] {
+            let shares = allocate(amount, &weights);
+            assert_eq!(shares.iter().map(|&share| share as u128).sum::<u128>(), amount as u128);
+        }
+    }
+
+    #[test]
+    fn shares_stay_within_one_unit_of_exact() {
+        // Pseudo-random weights, deterministic
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let weights: Vec<u64> = (0..257)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed % 1_000_000_007
+            })
+            .collect();
+        let total: u128 = weights.iter().map(|&weight| weight as u128).sum();
+
+        for amount in [1u64, 256, 257, 65_537, 123_456_789_012] {
+            let shares = allocate(amount, &weights);
+            assert_eq!(shares.iter().sum::<u64>(), amount);
+
+            for (share, weight) in shares.iter().zip(&weights) {
+                let floor = (amount as u128 * *weight as u128 / total) as u64;
+                assert!(*share == floor || *share == floor + 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use cp_amm::cpi::accounts::ClaimPositionFeeCtx;
+
+use crate::events::{DistributionSkipped, FeesClaimed, SkipReason};
+use crate::state::{Creator, DistributionPolicy, DistributionState, FeeLedger};
+use crate::error::ErrorCode;
+use crate::token::balance;
+
+/// Claims the fees of a DAMM v2 position, signed by its owner with `signer_seeds`, and returns
+/// the token A and B amounts claimed. Only the balance deltas are fees, anything else in the
+/// accounts isn't counted.
+pub fn claim_position_fees<'info>(accounts: ClaimPositionFeeCtx<'info>, signer_seeds: &[&[&[u8]]]) -> Result<(u64, u64)> {
+    let token_a_account = accounts.token_a_account.to_account_info();
+    let token_b_account = accounts.token_b_account.to_account_info();
+    let (balance_a, balance_b) = (balance(&token_a_account)?, balance(&token_b_account)?);
+
+    let ctx = CpiContext::new_with_signer(accounts.program.to_account_info(), accounts, signer_seeds);
+
+    cp_amm::cpi::claim_position_fee(ctx)?;
+
+    let amount_a = balance(&token_a_account)?.checked_sub(balance_a).ok_or(ProgramError::ArithmeticOverflow)?;
+    let amount_b = balance(&token_b_account)?.checked_sub(balance_b).ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok((amount_a, amount_b))
+}
+
+/// Claims the fees of the investor position into `pending_fees`, recorded in the fee ledger.
+/// The next distribution pays them out.
+pub fn claim_investor_position_fees<'info>(
+    accounts: ClaimPositionFeeCtx<'info>,
+    signer_seeds: &[&[&[u8]]],
+    distribution_state: &mut DistributionState,
+    fee_ledger: &mut FeeLedger,
+    launch: Pubkey,
+) -> Result<FeesClaimed> {
+    let (amount_a, amount_b) = claim_position_fees(accounts, signer_seeds)?;
+
+    distribution_state.pending_fees = distribution_state.pending_fees
+        .checked_add(amount_b)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    fee_ledger.record_claim(amount_a, amount_b)?;
+
+    // Fees claimed now go to the next distribution
+    Ok(FeesClaimed {
+        launch,
+        epoch: distribution_state.epoch.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?,
+        amount_a,
+        amount_b,
+    })
+}
+
+/// Checks the cooldown has passed since the last claim and records the claim at `now`. With only
+/// dust pending the distribution is skipped and the fees wait for the next one, the skip is
+/// returned; `None` means the pending fees are distributed now.
+pub fn distribution_skipped(
+    creator: &mut Account<Creator>,
+    distribution_policy: &DistributionPolicy,
+    distribution_state: &DistributionState,
+    now: i64,
+) -> Result<Option<DistributionSkipped>> {
+    let cooldown_end = creator.last_claimed_at
+        .checked_add(distribution_policy.cooldown)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(now >= cooldown_end, ErrorCode::CooldownActive);
+    creator.last_claimed_at = now;
+
+    if distribution_state.pending_fees >= distribution_policy.dust_threshold {
+        return Ok(None);
+    }
+
+    Ok(Some(DistributionSkipped {
+        launch: creator.key(),
+        epoch: distribution_state.epoch.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?,
+        reason: SkipReason::Dust,
+    }))
+}
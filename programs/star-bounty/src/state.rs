@@ -1,17 +1,1232 @@
 use anchor_lang::prelude::*;
+use streamflow_sdk::state::{Contract, CreateParams};
+
+use crate::error::ErrorCode;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Creator {
+    pub version: u8,
     //.. other fields
-    pub streams: u8,
+    pub streams: u32,
+    // Streams created before the u32 index, their metadata PDA uses a single byte seed
+    pub legacy_streams: u8,
+    pub last_claimed_at: i64,
+    // Bump of the `investor_fee_pos_owner` PDA that owns the investor position
+    pub position_owner_bump: [u8; 1],
     pub bump: [u8; 1],
 }
 
+impl Creator {
+    pub const VERSION: u8 = 2;
+    /// Size of the v1 layout: discriminator, `streams` and `bump`
+    pub const LEGACY_LEN: usize = 8 + 1 + 1;
+    /// Deposit of every stream created with the v1 layout, hardcoded in the old `create_stream`
+    pub const LEGACY_STREAM_AMOUNT: u64 = 1_000_000;
+
+    /// Index seed of the `metadata` PDA of stream `index`, little-endian u32
+    /// except for the streams created with the v1 layout.
+    pub fn stream_seed(&self, index: u32) -> Vec<u8> {
+        if self.legacy_stream(index) {
+            vec![index as u8]
+        } else {
+            index.to_le_bytes().to_vec()
+        }
+    }
+
+    /// Whether stream `index` was created with the v1 layout, top-ups enabled.
+    pub fn legacy_stream(&self, index: u32) -> bool {
+        index < self.legacy_streams as u32
+    }
+
+    /// Address of the `metadata` PDA of stream `index`.
+    pub fn stream_metadata(&self, creator: &Pubkey, index: u32) -> Pubkey {
+        Pubkey::find_program_address(&[
+            b"metadata",
+            creator.as_ref(),
+            &self.stream_seed(index),
+        ], &crate::ID).0
+    }
+}
+
+/// Legacy owner of the investor position, superseded by `Creator.last_claimed_at`.
+/// Only read by `migrate_creator`, its PDA keeps signing for the position.
 #[account]
 #[derive(InitSpace)]
 pub struct InvestorFeePositionOwnerPda {
     pub associated_mint: Pubkey,
     pub last_claimed_at: i64,
     pub bump: [u8; 1],
-}
\ No newline at end of file
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchConfig {
+    pub commitment_bps: u16,
+    pub pool_token_amount: u64,
+    pub decimals: u8,
+    pub paused: bool,
+    pub pause_reason: u16,
+    // Cancel/transfer rights streams of this launch are allowed to grant
+    pub stream_permissions: StreamPermissions,
+    // Owner of the token account receiving the creator remainder
+    pub treasury: Pubkey,
+    // Sum of the amounts deposited in the launch streams, Y0 before cancellations
+    pub total_deposited: u64,
+    // v1 streams of a migrated launch not added to `total_deposited` yet, Y0 can't be read before
+    pub legacy_streams_pending: u8,
+    // Pool activation, trading starts at `activation_point` or right away without one
+    pub activation_type: ActivationType,
+    pub activation_point: Option<u64>,
+    pub has_alpha_vault: bool,
+    pub bump: [u8; 1],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ActivationType {
+    Slot,
+    Timestamp,
+}
+
+impl ActivationType {
+    /// Current slot or timestamp, in the unit of the activation point
+    pub fn now(&self) -> Result<u64> {
+        let clock = Clock::get()?;
+
+        Ok(match self {
+            ActivationType::Slot => clock.slot,
+            ActivationType::Timestamp => clock.unix_timestamp as u64,
+        })
+    }
+}
+
+impl LaunchConfig {
+    /// Checks a stream about to be created against the launch and adds its amount
+    /// to the launch deposits.
+    pub fn register_stream(&mut self, permissions: &StreamPermissions, net_amount: u64) -> Result<()> {
+        require!(self.stream_permissions.allows(permissions), ErrorCode::InvalidStreamPermissions);
+
+        self.total_deposited = self.total_deposited.checked_add(net_amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Deserializes a Streamflow stream and checks it can't skew the distribution:
+    /// owned by Streamflow, paying `mint`, sent by one of the trusted stream senders,
+    /// without top-ups unless `legacy` and with the cancel/transfer rights allowed by the launch.
+    /// The address has to be checked against the creator stream PDA first.
+    pub fn load_stream(&self, global_config: &GlobalConfig, metadata: &AccountInfo, mint: &Pubkey, legacy: bool) -> Result<Contract> {
+        let stream = read_stream(global_config, metadata, mint, legacy)?;
+
+        require!(
+            self.stream_permissions.allows(&StreamPermissions::from(&stream.ix)),
+            ErrorCode::InvalidStreamPermissions
+        );
+
+        Ok(stream)
+    }
+
+    /// Loads a stream for a distribution. Ended and cancelled streams have nothing locked and
+    /// never block a distribution.
+    pub fn load_stream_status(&self, global_config: &GlobalConfig, metadata: &AccountInfo, mint: &Pubkey, legacy: bool) -> Result<StreamStatus> {
+        if stream_closed(metadata) {
+            return Ok(StreamStatus::Ended);
+        }
+
+        let stream = self.load_stream(global_config, metadata, mint, legacy)?;
+
+        Ok(if stream.canceled_at > 0 {
+            StreamStatus::Cancelled { unvested: unvested_at_cancel(&stream) }
+        } else if stream.closed {
+            StreamStatus::Ended
+        } else {
+            StreamStatus::Live(Box::new(stream))
+        })
+    }
+
+    /// Initial locked amount Y0: the launch deposits less the unvested remainder of the
+    /// cancelled streams, which went back to the sender.
+    pub fn initial_locked(&self, cancelled: u64) -> Result<u64> {
+        require!(self.legacy_streams_pending == 0, ErrorCode::LegacyStreamsPending);
+
+        Ok(self.total_deposited.saturating_sub(cancelled))
+    }
+}
+
+/// A launch stream as read by a distribution
+pub enum StreamStatus {
+    Live(Box<Contract>),
+    // Fully withdrawn, its deposit stays in Y0
+    Ended,
+    // Cancelled, `unvested` went back to the sender and leaves Y0
+    Cancelled { unvested: u64 },
+}
+
+/// Deserializes a Streamflow stream owned by Streamflow, paying `mint`, sent by one of the
+/// trusted stream senders and without top-ups, whatever its cancel/transfer rights. The v1
+/// `create_stream` enabled top-ups on every stream, `legacy` streams are let through but only
+/// count for their v1 deposit, whatever was topped up since.
+pub fn read_stream(global_config: &GlobalConfig, metadata: &AccountInfo, mint: &Pubkey, legacy: bool) -> Result<Contract> {
+    require_keys_eq!(*metadata.owner, streamflow_sdk::id(), ErrorCode::InvalidStreamOwner);
+
+    let mut stream = Contract::deserialize(&mut metadata.try_borrow_data()?.as_ref())?;
+
+    require_keys_eq!(stream.mint, *mint, ErrorCode::InvalidStreamMint);
+    require!(global_config.stream_senders.contains(&stream.sender), ErrorCode::InvalidStreamSender);
+
+    if legacy {
+        // Top-ups extend the schedule without changing the rate, capping the deposit caps the weight
+        stream.ix.net_amount_deposited = std::cmp::min(stream.ix.net_amount_deposited, Creator::LEGACY_STREAM_AMOUNT);
+    } else {
+        require!(!stream.ix.can_topup, ErrorCode::StreamTopupEnabled);
+    }
+
+    Ok(stream)
+}
+
+/// Whether Streamflow closed the stream metadata account, which it does once a stream is
+/// cancelled or fully withdrawn. A closed stream can't be told apart from a completed one, so
+/// it's treated as completed. The address has to be checked against the stream PDA first.
+pub fn stream_closed(metadata: &AccountInfo) -> bool {
+    metadata.owner == &anchor_lang::system_program::ID && metadata.data_is_empty()
+}
+
+/// Whether the stream ended early: cancelled, or closed with its metadata still around.
+pub fn stream_terminated(stream: &Contract) -> bool {
+    stream.closed || stream.canceled_at > 0
+}
+
+/// Amount of the stream vested at `at`, cliff included and capped at the deposit. Withdrawals
+/// don't change it. Streamflow's `vested_available` underflows before the stream starts.
+pub fn vested_amount(stream: &Contract, at: u64) -> u64 {
+    let net = stream.ix.net_amount_deposited;
+
+    if at < stream.start_time() {
+        return 0;
+    }
+    if at >= stream.end_time && stream.current_pause_start == 0 {
+        return net;
+    }
+
+    // Only what had unlocked by the last rate change is known before it
+    let streamed = if at < stream.effective_start_time() {
+        stream.funds_unlocked_at_last_rate_change
+    } else {
+        stream.vested_available(at)
+    };
+
+    std::cmp::min(net, streamed.saturating_add(stream.cliff_available(at)))
+}
+
+/// Amount of a cancelled stream that hadn't vested when it was cancelled.
+pub fn unvested_at_cancel(stream: &Contract) -> u64 {
+    stream.ix.net_amount_deposited - vested_amount(stream, stream.canceled_at)
+}
+
+/// Amount of the stream still locked at `now`, nothing once it's terminated.
+pub fn locked_amount(stream: &Contract, now: u64) -> u64 {
+    if stream_terminated(stream) {
+        return 0;
+    }
+
+    stream.ix.net_amount_deposited - vested_amount(stream, now)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamPermissions {
+    pub cancelable_by_sender: bool,
+    pub cancelable_by_recipient: bool,
+    pub transferable_by_sender: bool,
+    pub transferable_by_recipient: bool,
+}
+
+impl StreamPermissions {
+    /// True if `other` doesn't grant any right missing from `self`.
+    pub fn allows(&self, other: &StreamPermissions) -> bool {
+        (self.cancelable_by_sender || !other.cancelable_by_sender)
+            && (self.cancelable_by_recipient || !other.cancelable_by_recipient)
+            && (self.transferable_by_sender || !other.transferable_by_sender)
+            && (self.transferable_by_recipient || !other.transferable_by_recipient)
+    }
+
+    /// Rights granted by either `self` or `other`.
+    pub fn union(&self, other: &StreamPermissions) -> StreamPermissions {
+        StreamPermissions {
+            cancelable_by_sender: self.cancelable_by_sender || other.cancelable_by_sender,
+            cancelable_by_recipient: self.cancelable_by_recipient || other.cancelable_by_recipient,
+            transferable_by_sender: self.transferable_by_sender || other.transferable_by_sender,
+            transferable_by_recipient: self.transferable_by_recipient || other.transferable_by_recipient,
+        }
+    }
+}
+
+impl From<&CreateParams> for StreamPermissions {
+    fn from(ix: &CreateParams) -> Self {
+        Self {
+            cancelable_by_sender: ix.cancelable_by_sender,
+            cancelable_by_recipient: ix.cancelable_by_recipient,
+            transferable_by_sender: ix.transferable_by_sender,
+            transferable_by_recipient: ix.transferable_by_recipient,
+        }
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct DistributionPolicy {
+    pub dust_threshold: u64,
+    pub max_investor_share_bps: u16,
+    pub daily_cap: u64,
+    pub cooldown: i64,
+    pub mode: DistributionMode,
+    // Keep the capped excess and rounding dust for investors instead of sweeping it
+    pub carry_over: bool,
+    // How `claim_fees` rounds the pro-rata investor shares
+    pub rounding: Rounding,
+    pub bump: [u8; 1],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    // Every share rounded down, the dust isn't paid
+    Floor,
+    // Dust handed out to the largest remainders, shares add up to the investor amount
+    LargestRemainder,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DistributionMode {
+    // The crank transfers every investor share, `claim_fees` or the paginated epoch
+    Push,
+    // The crank bumps `FeeIndex`, investors pull with `claim_investor_fees`
+    Pull,
+}
+
+impl DistributionPolicy {
+    pub const DUST_THRESHOLD: u64 = 1_000_000; // 1 Token
+    pub const MAX_INVESTOR_SHARE_BPS: u16 = 1_000; // 10%
+    pub const DAILY_CAP: u64 = 1_000_000_000; // 1000 Tokens
+    pub const COOLDOWN: i64 = 86_400; // 24h
+
+    /// Default investor terms of a new launch, the admin can change them later
+    /// with `update_distribution_policy`.
+    pub fn new(bump: [u8; 1]) -> Self {
+        Self {
+            dust_threshold: Self::DUST_THRESHOLD,
+            max_investor_share_bps: Self::MAX_INVESTOR_SHARE_BPS,
+            daily_cap: Self::DAILY_CAP,
+            cooldown: Self::COOLDOWN,
+            mode: DistributionMode::Push,
+            carry_over: false,
+            rounding: Rounding::Floor,
+            bump,
+        }
+    }
+
+    /// Investor part of `amount` before the daily cap: the locked share of the
+    /// streams (Y / Y0) capped by `max_investor_share_bps`.
+    pub fn investor_quote(&self, amount: u64, total_locked: u64, initial_locked: u64) -> Result<u64> {
+        let (_, eligible_investor_share_bps) = self.investor_share_bps(total_locked, initial_locked)?;
+
+        // Cacluate the investor share
+        let investor_fee_quote = (amount as u128)
+            .checked_mul(eligible_investor_share_bps as u128)
+            .and_then(|x| x.checked_div(10_000))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(investor_fee_quote as u64)
+    }
+
+    /// Locked share of the streams `f_locked_bps` and the eligible investor share, both in bps.
+    /// Nothing is eligible without deposits.
+    pub fn investor_share_bps(&self, total_locked: u64, initial_locked: u64) -> Result<(u64, u64)> {
+        if initial_locked == 0 {
+            return Ok((0, 0));
+        }
+
+        // Calulate the eligibile investor share
+        let f_locked_bps = (total_locked as u128)
+            .checked_mul(10_000)
+            .and_then(|x| x.checked_div(initial_locked as u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let f_locked_bps = u64::try_from(f_locked_bps)?;
+
+        Ok((f_locked_bps, std::cmp::min(self.max_investor_share_bps as u64, f_locked_bps)))
+    }
+
+    /// What investors are owed for `quote` new fees and the `reserve` still owed from previous
+    /// distributions. The reserve is only ever kept for investors, so it's owed with or without carry-over.
+    pub fn investor_owed(&self, quote: u64, reserve: u64) -> Result<u64> {
+        Ok(quote.checked_add(reserve).ok_or(ProgramError::ArithmeticOverflow)?)
+    }
+
+    /// Part of `owed` paid out in a single distribution.
+    pub fn investor_distributable(&self, owed: u64) -> u64 {
+        std::cmp::min(owed, self.daily_cap)
+    }
+
+    /// Reserve left once `paid` out of `owed` went to investors, `reserve` being what was carried
+    /// into the distribution. Payouts settle the reserve first. Without carry-over only the unpaid
+    /// part of that reserve is kept, the unpaid new fees go to the treasury. Without locked streams
+    /// to pay it to, nothing is kept.
+    pub fn investor_reserve(&self, owed: u64, paid: u64, reserve: u64, total_locked: u64) -> u64 {
+        if total_locked == 0 {
+            return 0;
+        }
+
+        if self.carry_over {
+            owed.saturating_sub(paid)
+        } else {
+            reserve.saturating_sub(paid)
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DistributionPhase {
+    Idle,
+    // Pages add their locked amounts to the epoch totals
+    Tallying,
+    // Pages pay their streams pro-rata
+    Paying,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DistributionState {
+    pub epoch: u64,
+    pub phase: DistributionPhase,
+    // Locked amounts of the whole epoch are computed at this timestamp
+    pub started_at: i64,
+    // Stream count frozen at the start of the epoch
+    pub streams: u32,
+    // New fees frozen at the start of the epoch, reserve excluded
+    pub claimed: u64,
+    pub initial_locked: u64,
+    pub total_locked: u64,
+    // Investor part of `claimed` plus the carried reserve, known once every page is tallied
+    pub owed: u64,
+    // Part of `owed` paid in this epoch
+    pub distributable: u64,
+    // Shares paid so far, taken out of `owed`
+    pub settled: u64,
+    // Received by the streams, the shares less the transfer fees the treasury amount couldn't cover
+    pub distributed: u64,
+    // Transfer fees withheld on the payouts of the epoch
+    pub transfer_fees: u64,
+    // Investor fees carried over between distributions, kept in the position owner account
+    pub investor_reserve: u64,
+    // Token B fees claimed from the position and not distributed yet, measured as balance deltas
+    pub pending_fees: u64,
+    pub tallied_pages: [u8; 64],
+    pub paid_pages: [u8; 64],
+    pub bump: [u8; 1],
+}
+
+impl DistributionState {
+    pub const PAGE_SIZE: u32 = 8;
+    pub const MAX_PAGES: u32 = 64 * 8;
+
+    pub fn new(bump: [u8; 1]) -> Self {
+        Self {
+            epoch: 0,
+            phase: DistributionPhase::Idle,
+            started_at: 0,
+            streams: 0,
+            claimed: 0,
+            initial_locked: 0,
+            total_locked: 0,
+            owed: 0,
+            distributable: 0,
+            settled: 0,
+            distributed: 0,
+            transfer_fees: 0,
+            investor_reserve: 0,
+            pending_fees: 0,
+            tallied_pages: [0; 64],
+            paid_pages: [0; 64],
+            bump,
+        }
+    }
+
+    pub fn pages(&self) -> u32 {
+        self.streams.div_ceil(Self::PAGE_SIZE)
+    }
+
+    /// Marks the pages covering `[start_index, start_index + count)` in `bitmap`. Ranges
+    /// start on a page boundary and cover whole pages, except the last one.
+    pub fn mark_pages(&self, bitmap: &mut [u8; 64], start_index: u32, count: u32) -> Result<()> {
+        let end = start_index.checked_add(count).ok_or(ProgramError::ArithmeticOverflow)?;
+
+        require!(count > 0 && end <= self.streams, ErrorCode::InvalidPage);
+        require!(start_index % Self::PAGE_SIZE == 0, ErrorCode::InvalidPage);
+        require!(count % Self::PAGE_SIZE == 0 || end == self.streams, ErrorCode::InvalidPage);
+
+        for page in start_index / Self::PAGE_SIZE..end.div_ceil(Self::PAGE_SIZE) {
+            let (byte, bit) = ((page / 8) as usize, 1u8 << (page % 8));
+            require!(bitmap[byte] & bit == 0, ErrorCode::PageAlreadyProcessed);
+            bitmap[byte] |= bit;
+        }
+
+        Ok(())
+    }
+
+    pub fn all_pages_done(&self, bitmap: &[u8; 64]) -> bool {
+        (0..self.pages()).all(|page| bitmap[(page / 8) as usize] & (1u8 << (page % 8)) != 0)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeIndex {
+    // Cumulative investor fees per locked unit, scaled by `PRECISION`
+    pub fee_per_locked_unit: u128,
+    // Sum of the checkpoint weights, each refreshed when its stream registers or claims
+    pub total_weight: u64,
+    // Registered checkpoints, accruals wait for every stream of the launch
+    pub registered: u32,
+    // Unvested remainder of the cancelled streams their checkpoints saw, it leaves Y0
+    pub cancelled: u64,
+    // Investor fees accrued and not claimed yet, kept in the position owner account
+    pub unclaimed: u64,
+    // Transfer fees of the unclaimed fees, kept back from the treasury part for the claims
+    pub transfer_fee_budget: u64,
+    pub bump: [u8; 1],
+}
+
+impl FeeIndex {
+    pub const PRECISION: u128 = 1_000_000_000_000;
+
+    pub fn new(bump: [u8; 1]) -> Self {
+        Self {
+            fee_per_locked_unit: 0,
+            total_weight: 0,
+            registered: 0,
+            cancelled: 0,
+            unclaimed: 0,
+            transfer_fee_budget: 0,
+            bump,
+        }
+    }
+
+    /// Fees owed to `weight` since the `fee_per_locked_unit` snapshot `from`.
+    pub fn owed(&self, from: u128, weight: u64) -> Result<u64> {
+        let owed = self.fee_per_locked_unit
+            .checked_sub(from)
+            .and_then(|x| x.checked_mul(weight as u128))
+            .map(|x| x / Self::PRECISION)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(u64::try_from(owed)?)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StreamCheckpoint {
+    // `FeeIndex.fee_per_locked_unit` when the checkpoint was last settled
+    pub fee_per_locked_unit: u128,
+    // Locked amount of the stream when the checkpoint was last refreshed
+    pub weight: u64,
+    // Fees settled and not claimed yet
+    pub pending: u64,
+    // Token account of the stream recipient when last read, paid once Streamflow closed the stream
+    pub recipient_tokens: Pubkey,
+    // The stream ended or was cancelled, its weight stays 0
+    pub terminated: bool,
+    pub bump: [u8; 1],
+}
+
+impl StreamCheckpoint {
+    /// Moves the fees accrued on the weight since the last settlement to `pending`.
+    pub fn settle(&mut self, fee_index: &FeeIndex) -> Result<()> {
+        let owed = fee_index.owed(self.fee_per_locked_unit, self.weight)?;
+
+        self.pending = self.pending.checked_add(owed).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.fee_per_locked_unit = fee_index.fee_per_locked_unit;
+
+        Ok(())
+    }
+
+    /// Settles the checkpoint and sets its weight to what the stream `status` has locked at `now`,
+    /// keeping `FeeIndex.total_weight` in sync. The unvested remainder of a cancelled stream is
+    /// added to `FeeIndex.cancelled` the first time it's seen.
+    pub fn refresh(&mut self, fee_index: &mut FeeIndex, status: &StreamStatus, now: u64) -> Result<()> {
+        self.settle(fee_index)?;
+
+        let weight = match status {
+            StreamStatus::Live(stream) => locked_amount(stream, now),
+            StreamStatus::Ended => 0,
+            StreamStatus::Cancelled { unvested } => {
+                if !self.terminated {
+                    fee_index.cancelled = fee_index.cancelled.checked_add(*unvested).ok_or(ProgramError::ArithmeticOverflow)?;
+                }
+                0
+            }
+        };
+
+        fee_index.total_weight = fee_index.total_weight
+            .checked_sub(self.weight)
+            .and_then(|x| x.checked_add(weight))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.weight = weight;
+        self.terminated = !matches!(status, StreamStatus::Live(_));
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeLedger {
+    // Lifetime fees claimed from the investor position
+    pub claimed_a: u128,
+    pub claimed_b: u128,
+    // Lifetime token B received by investors
+    pub distributed: u128,
+    // Lifetime token B sent to the treasury by distributions
+    pub sent_to_creator: u128,
+    // Lifetime token B withheld by the mint on investor payouts
+    pub transfer_fees: u128,
+    pub epochs: u64,
+    pub last_epoch_at: i64,
+    pub bump: [u8; 1],
+}
+
+impl FeeLedger {
+    pub fn new(bump: [u8; 1]) -> Self {
+        Self {
+            claimed_a: 0,
+            claimed_b: 0,
+            distributed: 0,
+            sent_to_creator: 0,
+            transfer_fees: 0,
+            epochs: 0,
+            last_epoch_at: 0,
+            bump,
+        }
+    }
+
+    pub fn record_claim(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.claimed_a = self.claimed_a.checked_add(amount_a as u128).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.claimed_b = self.claimed_b.checked_add(amount_b as u128).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_epoch(&mut self, now: i64) -> Result<()> {
+        self.epochs = self.epochs.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.last_epoch_at = now;
+        Ok(())
+    }
+
+    pub fn record_payout(&mut self, distributed: u64, sent_to_creator: u64, transfer_fees: u64) -> Result<()> {
+        self.distributed = self.distributed.checked_add(distributed as u128).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.sent_to_creator = self.sent_to_creator.checked_add(sent_to_creator as u128).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.transfer_fees = self.transfer_fees.checked_add(transfer_fees as u128).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StreamLedger {
+    // Lifetime token B paid to the stream recipient
+    pub paid: u128,
+    pub last_paid_at: i64,
+    pub bump: [u8; 1],
+}
+
+impl StreamLedger {
+    /// Loads the ledger of the stream `metadata` from `info`, `None` if it's the ledger of another stream
+    fn load(info: &AccountInfo, metadata: &Pubkey) -> Result<Option<Self>> {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidStreamLedger);
+        let ledger = StreamLedger::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+
+        // With another stream's seeds the bump can land on the curve
+        let address = Pubkey::create_program_address(&[b"stream_ledger", metadata.as_ref(), &ledger.bump], &crate::ID).ok();
+        Ok((address == Some(info.key())).then_some(ledger))
+    }
+
+    fn add(mut self, info: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+        self.paid = self.paid.checked_add(amount as u128).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.last_paid_at = now;
+
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+
+    /// Adds a payout to the ledger of the stream `metadata` passed as `info`, which the context
+    /// checked against the stream ledger PDA. The payout is left out while the ledger isn't initialized.
+    pub fn record(info: &AccountInfo, metadata: &Pubkey, amount: u64, now: i64) -> Result<()> {
+        if info.owner == &anchor_lang::system_program::ID {
+            return Ok(());
+        }
+
+        let ledger = Self::load(info, metadata)?.ok_or(ErrorCode::InvalidStreamLedger)?;
+        ledger.add(info, amount, now)
+    }
+}
+
+/// The stream ledgers a distribution is passed after its streams. Any subset of the streams can be
+/// passed, in stream order, and only those ledgers are updated.
+pub struct StreamLedgers<'a, 'info> {
+    accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> StreamLedgers<'a, 'info> {
+    pub fn new(accounts: &'a [AccountInfo<'info>]) -> Self {
+        Self { accounts }
+    }
+
+    /// Adds the payout of the stream `metadata` to its ledger if it's the next one passed. Called for
+    /// every stream in order, paid or not.
+    pub fn record(&mut self, metadata: &Pubkey, amount: u64, now: i64) -> Result<()> {
+        let Some((info, rest)) = self.accounts.split_first() else {
+            return Ok(());
+        };
+
+        if let Some(ledger) = StreamLedger::load(info, metadata)? {
+            if amount > 0 {
+                ledger.add(info, amount, now)?;
+            }
+            self.accounts = rest;
+        }
+
+        Ok(())
+    }
+
+    /// Every ledger passed has to belong to one of the streams, in order
+    pub fn finish(&self) -> Result<()> {
+        require!(self.accounts.is_empty(), ErrorCode::InvalidStreamLedger);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorFeeSplit {
+    // Team wallets sharing the creator position fees, the treasury gets the rest
+    #[max_len(8)]
+    pub recipients: Vec<FeeRecipient>,
+    pub bump: [u8; 1],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeRecipient {
+    pub wallet: Pubkey,
+    pub bps: u16,
+}
+
+impl CreatorFeeSplit {
+    pub const MAX_RECIPIENTS: usize = 8;
+}
+
+impl FeeRecipient {
+    /// Share of `amount` going to the recipient, rounded down so the treasury keeps the dust.
+    pub fn share(&self, amount: u64) -> u64 {
+        (amount as u128 * self.bps as u128 / 10_000) as u64
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub stream_manager: Pubkey,
+    pub position_manager: Pubkey,
+    pub pauser: Pubkey,
+    pub treasury_manager: Pubkey,
+    // None keeps claim_fees permissionless
+    pub cranker: Option<Pubkey>,
+    pub paused: bool,
+    pub pause_reason: u16,
+    // Keys distributions accept as stream sender, managed by the admin
+    #[max_len(8)]
+    pub stream_senders: Vec<Pubkey>,
+    pub bump: [u8; 1],
+}
+
+impl GlobalConfig {
+    pub const MAX_STREAM_SENDERS: usize = 8;
+    /// Hardcoded admin that sent every stream of the v1 launches
+    pub const LEGACY_STREAM_SENDER: Pubkey = pubkey!("1oksyAnDKAFv4qgDDrwDh2XDHNM4vhXziXo5Qb5Rnmc");
+
+    /// Returns the key currently holding `role`, `Pubkey::default()` if revoked.
+    pub fn role(&self, role: Role) -> Pubkey {
+        match role {
+            Role::StreamManager => self.stream_manager,
+            Role::PositionManager => self.position_manager,
+            Role::Pauser => self.pauser,
+            Role::TreasuryManager => self.treasury_manager,
+            Role::Cranker => self.cranker.unwrap_or_default(),
+        }
+    }
+
+    pub fn set_role(&mut self, role: Role, account: Pubkey) {
+        match role {
+            Role::StreamManager => self.stream_manager = account,
+            Role::PositionManager => self.position_manager = account,
+            Role::Pauser => self.pauser = account,
+            Role::TreasuryManager => self.treasury_manager = account,
+            Role::Cranker => self.cranker = (account != Pubkey::default()).then_some(account),
+        }
+    }
+
+    /// Trusts the streams sent by `sender`, they keep counting after a rotation. Returns false
+    /// if it was already trusted.
+    pub fn add_stream_sender(&mut self, sender: Pubkey) -> Result<bool> {
+        if self.stream_senders.contains(&sender) {
+            return Ok(false);
+        }
+
+        require!(self.stream_senders.len() < Self::MAX_STREAM_SENDERS, ErrorCode::TooManyStreamSenders);
+        self.stream_senders.push(sender);
+
+        Ok(true)
+    }
+
+    /// Stops trusting the streams sent by `sender`. The current stream manager can't be
+    /// removed, the streams it creates would fail every distribution.
+    pub fn remove_stream_sender(&mut self, sender: Pubkey) -> Result<()> {
+        require_keys_neq!(sender, self.stream_manager, ErrorCode::StreamSenderInUse);
+
+        let index = self.stream_senders
+            .iter()
+            .position(|key| *key == sender)
+            .ok_or(ErrorCode::StreamSenderNotFound)?;
+        self.stream_senders.remove(index);
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    StreamManager,
+    PositionManager,
+    Pauser,
+    TreasuryManager,
+    Cranker,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::CreateStreamArgs;
+
+    fn state(streams: u32) -> DistributionState {
+        DistributionState {
+            streams,
+            ..DistributionState::new([0])
+        }
+    }
+
+    #[test]
+    fn marks_whole_pages() {
+        let state = state(24);
+        let mut bitmap = [0u8; 64];
+
+        state.mark_pages(&mut bitmap, 0, 16).unwrap();
+        assert_eq!(bitmap[0], 0b011);
+        assert!(!state.all_pages_done(&bitmap));
+
+        state.mark_pages(&mut bitmap, 16, 8).unwrap();
+        assert_eq!(bitmap[0], 0b111);
+        assert!(state.all_pages_done(&bitmap));
+    }
+
+    #[test]
+    fn partial_last_page() {
+        let state = state(20);
+        let mut bitmap = [0u8; 64];
+
+        // The last page only holds streams 16..20
+        state.mark_pages(&mut bitmap, 16, 4).unwrap();
+        state.mark_pages(&mut bitmap, 0, 16).unwrap();
+        assert!(state.all_pages_done(&bitmap));
+
+        // Short of the last stream, or past it
+        let mut bitmap = [0u8; 64];
+        assert_eq!(state.mark_pages(&mut bitmap, 16, 3).unwrap_err(), ErrorCode::InvalidPage.into());
+        assert_eq!(state.mark_pages(&mut bitmap, 16, 8).unwrap_err(), ErrorCode::InvalidPage.into());
+        assert_eq!(bitmap, [0u8; 64]);
+    }
+
+    #[test]
+    fn rejects_misaligned_ranges() {
+        let state = state(24);
+        let mut bitmap = [0u8; 64];
+
+        assert_eq!(state.mark_pages(&mut bitmap, 4, 8).unwrap_err(), ErrorCode::InvalidPage.into());
+        assert_eq!(state.mark_pages(&mut bitmap, 0, 12).unwrap_err(), ErrorCode::InvalidPage.into());
+        assert_eq!(state.mark_pages(&mut bitmap, 0, 0).unwrap_err(), ErrorCode::InvalidPage.into());
+        assert_eq!(bitmap, [0u8; 64]);
+    }
+
+    #[test]
+    fn rejects_double_marking() {
+        let state = state(24);
+        let mut bitmap = [0u8; 64];
+
+        state.mark_pages(&mut bitmap, 8, 8).unwrap();
+        assert_eq!(state.mark_pages(&mut bitmap, 8, 8).unwrap_err(), ErrorCode::PageAlreadyProcessed.into());
+        assert_eq!(state.mark_pages(&mut bitmap, 0, 16).unwrap_err(), ErrorCode::PageAlreadyProcessed.into());
+    }
+
+    #[test]
+    fn pages_past_the_first_byte() {
+        let state = state(DistributionState::MAX_PAGES * DistributionState::PAGE_SIZE);
+        let mut bitmap = [0u8; 64];
+
+        state.mark_pages(&mut bitmap, 0, state.streams).unwrap();
+        assert_eq!(bitmap, [0xff; 64]);
+        assert!(state.all_pages_done(&bitmap));
+    }
+
+    #[test]
+    fn no_streams_is_done() {
+        assert!(state(0).all_pages_done(&[0u8; 64]));
+    }
+
+    fn fee_index(fee_per_locked_unit: u128) -> FeeIndex {
+        FeeIndex {
+            fee_per_locked_unit,
+            ..FeeIndex::new([0])
+        }
+    }
+
+    fn checkpoint(fee_per_locked_unit: u128, weight: u64) -> StreamCheckpoint {
+        StreamCheckpoint {
+            fee_per_locked_unit,
+            weight,
+            pending: 0,
+            recipient_tokens: Pubkey::default(),
+            terminated: false,
+            bump: [0],
+        }
+    }
+
+    #[test]
+    fn settle_moves_the_accrued_fees_to_pending() {
+        // 0.5 token per locked unit since the checkpoint
+        let index = fee_index(FeeIndex::PRECISION / 2);
+        let mut checkpoint = checkpoint(0, 1_000);
+
+        checkpoint.settle(&index).unwrap();
+        assert_eq!(checkpoint.pending, 500);
+        assert_eq!(checkpoint.fee_per_locked_unit, FeeIndex::PRECISION / 2);
+
+        // Nothing accrued since
+        checkpoint.settle(&index).unwrap();
+        assert_eq!(checkpoint.pending, 500);
+    }
+
+    #[test]
+    fn settle_keeps_each_weight_to_its_accruals() {
+        let mut checkpoint = checkpoint(0, 1_000);
+
+        // Accrued on 1000 locked, then refreshed to 400 before the next accrual
+        checkpoint.settle(&fee_index(FeeIndex::PRECISION / 4)).unwrap();
+        checkpoint.weight = 400;
+        checkpoint.settle(&fee_index(FeeIndex::PRECISION)).unwrap();
+
+        assert_eq!(checkpoint.pending, 250 + 300);
+    }
+
+    #[test]
+    fn refresh_keeps_the_total_weight_in_sync() {
+        let live = stream(Pubkey::new_unique(), Pubkey::new_unique(), false);
+        let mut index = fee_index(0);
+        let (mut a, mut b) = (checkpoint(0, 0), checkpoint(0, 0));
+
+        a.refresh(&mut index, &StreamStatus::Live(Box::new(live.clone())), 1_000).unwrap();
+        b.refresh(&mut index, &StreamStatus::Live(Box::new(live.clone())), 1_000).unwrap();
+        assert_eq!(index.total_weight, 2_000);
+
+        // `a` refreshed half vested, `b` still counts for its last refresh
+        index.fee_per_locked_unit = FeeIndex::PRECISION;
+        a.refresh(&mut index, &StreamStatus::Live(Box::new(live)), 1_050).unwrap();
+        assert_eq!((a.pending, a.weight), (1_000, 500));
+        assert_eq!(index.total_weight, 1_500);
+
+        b.refresh(&mut index, &StreamStatus::Ended, 1_050).unwrap();
+        assert_eq!((b.pending, b.weight, b.terminated), (1_000, 0, true));
+        assert_eq!(index.total_weight, 500);
+    }
+
+    #[test]
+    fn cancelled_streams_leave_y0_once() {
+        let mut index = fee_index(0);
+        let mut checkpoint = checkpoint(0, 0);
+
+        checkpoint.refresh(&mut index, &StreamStatus::Cancelled { unvested: 300 }, 1_000).unwrap();
+        checkpoint.refresh(&mut index, &StreamStatus::Cancelled { unvested: 300 }, 1_050).unwrap();
+        assert_eq!(index.cancelled, 300);
+        assert_eq!(index.total_weight, 0);
+    }
+
+    fn global_config(stream_manager: Pubkey) -> GlobalConfig {
+        GlobalConfig {
+            admin: stream_manager,
+            pending_admin: None,
+            stream_manager,
+            position_manager: stream_manager,
+            pauser: stream_manager,
+            treasury_manager: stream_manager,
+            cranker: None,
+            paused: false,
+            pause_reason: 0,
+            stream_senders: vec![GlobalConfig::LEGACY_STREAM_SENDER, stream_manager],
+            bump: [0],
+        }
+    }
+
+    #[test]
+    fn stream_senders_rotate_within_the_cap() {
+        let manager = Pubkey::new_unique();
+        let mut config = global_config(manager);
+
+        assert!(!config.add_stream_sender(manager).unwrap());
+        for _ in 2..GlobalConfig::MAX_STREAM_SENDERS {
+            assert!(config.add_stream_sender(Pubkey::new_unique()).unwrap());
+        }
+        assert_eq!(config.add_stream_sender(Pubkey::new_unique()).unwrap_err(), ErrorCode::TooManyStreamSenders.into());
+
+        // Pruning a former key makes room for the next manager
+        config.remove_stream_sender(GlobalConfig::LEGACY_STREAM_SENDER).unwrap();
+        assert!(config.add_stream_sender(Pubkey::new_unique()).unwrap());
+        assert!(!config.stream_senders.contains(&GlobalConfig::LEGACY_STREAM_SENDER));
+    }
+
+    fn stream(mint: Pubkey, sender: Pubkey, can_topup: bool) -> Contract {
+        Contract {
+            magic: 0,
+            version: 0,
+            created_at: 0,
+            amount_withdrawn: 0,
+            canceled_at: 0,
+            end_time: 1_100,
+            last_withdrawn_at: 0,
+            sender,
+            sender_tokens: Pubkey::default(),
+            recipient: Pubkey::new_unique(),
+            recipient_tokens: Pubkey::new_unique(),
+            mint,
+            escrow_tokens: Pubkey::default(),
+            streamflow_treasury: Pubkey::default(),
+            streamflow_treasury_tokens: Pubkey::default(),
+            streamflow_fee_total: 0,
+            streamflow_fee_withdrawn: 0,
+            streamflow_fee_percent: 0.0,
+            partner: Pubkey::default(),
+            partner_tokens: Pubkey::default(),
+            partner_fee_total: 0,
+            partner_fee_withdrawn: 0,
+            partner_fee_percent: 0.0,
+            // 1000 vesting over 100 periods of 1s from 1000
+            ix: CreateParams {
+                start_time: 1_000,
+                net_amount_deposited: 1_000,
+                period: 1,
+                amount_per_period: 10,
+                cliff: 0,
+                cliff_amount: 0,
+                cancelable_by_sender: false,
+                cancelable_by_recipient: false,
+                automatic_withdrawal: false,
+                transferable_by_sender: false,
+                transferable_by_recipient: false,
+                can_topup,
+                stream_name: [0; 64],
+                withdraw_frequency: 0,
+                ghost: 0,
+                pausable: false,
+                can_update_rate: false,
+            },
+            ix_padding: vec![],
+            closed: false,
+            current_pause_start: 0,
+            pause_cumulative: 0,
+            last_rate_change_time: 0,
+            funds_unlocked_at_last_rate_change: 0,
+        }
+    }
+
+    /// Reads `stream` the way the program reads a `metadata` account owned by Streamflow.
+    fn read(global_config: &GlobalConfig, stream: &Contract, legacy: bool) -> Result<Contract> {
+        let (key, owner) = (Pubkey::new_unique(), streamflow_sdk::id());
+        let mut lamports = 0;
+        let mut data = stream.try_to_vec().unwrap();
+        let metadata = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        read_stream(global_config, &metadata, &stream.mint, legacy)
+    }
+
+    #[test]
+    fn top_ups_only_pass_on_v1_streams() {
+        let config = global_config(Pubkey::new_unique());
+        let stream = stream(Pubkey::new_unique(), GlobalConfig::LEGACY_STREAM_SENDER, true);
+
+        assert_eq!(read(&config, &stream, false).unwrap_err(), ErrorCode::StreamTopupEnabled.into());
+        assert!(read(&config, &stream, true).is_ok());
+    }
+
+    #[test]
+    fn loads_topped_up_v1_streams_at_their_v1_deposit() {
+        let config = global_config(Pubkey::new_unique());
+        let mut stream = stream(Pubkey::new_unique(), GlobalConfig::LEGACY_STREAM_SENDER, true);
+
+        stream.ix.net_amount_deposited = Creator::LEGACY_STREAM_AMOUNT;
+        assert_eq!(read(&config, &stream, true).unwrap().ix.net_amount_deposited, Creator::LEGACY_STREAM_AMOUNT);
+
+        // Topped up since the migration
+        stream.ix.net_amount_deposited += 500_000;
+        assert_eq!(read(&config, &stream, true).unwrap().ix.net_amount_deposited, Creator::LEGACY_STREAM_AMOUNT);
+    }
+
+    #[test]
+    fn nothing_vests_before_the_start() {
+        let mut stream = stream(Pubkey::new_unique(), Pubkey::new_unique(), false);
+
+        assert_eq!(locked_amount(&stream, 500), 1_000);
+
+        // Cancelled before it started, the whole deposit went back
+        stream.canceled_at = 500;
+        assert_eq!(unvested_at_cancel(&stream), 1_000);
+    }
+
+    #[test]
+    fn streams_starting_later_stay_locked() {
+        let now = 1_000_000;
+        let args = CreateStreamArgs {
+            start_time: now + 86_400,
+            net_amount: 1_000,
+            period: 1,
+            amount_per_period: 10,
+            cliff: now + 2 * 86_400,
+            cliff_amount: 0,
+            name: [0; 64],
+            cancelable_by_sender: false,
+            cancelable_by_recipient: false,
+            transferable_by_sender: false,
+            transferable_by_recipient: false,
+        };
+        args.validate(now as i64).unwrap();
+
+        let mut stream = stream(Pubkey::new_unique(), Pubkey::new_unique(), false);
+        stream.ix.start_time = args.start_time;
+        stream.ix.cliff = args.cliff;
+        stream.end_time = args.cliff + 100;
+
+        // Nothing vests before the start, nor between the start and the cliff
+        assert_eq!(locked_amount(&stream, now), 1_000);
+        assert_eq!(locked_amount(&stream, args.cliff - 1), 1_000);
+        assert_eq!(locked_amount(&stream, args.cliff + 50), 500);
+    }
+
+    #[test]
+    fn withdrawals_dont_count_twice() {
+        let mut stream = stream(Pubkey::new_unique(), Pubkey::new_unique(), false);
+
+        // Half vested, 300 of it withdrawn
+        stream.amount_withdrawn = 300;
+        assert_eq!(locked_amount(&stream, 1_050), 500);
+
+        stream.canceled_at = 1_050;
+        assert_eq!(unvested_at_cancel(&stream), 500);
+    }
+
+    #[test]
+    fn fully_vested_streams_have_nothing_locked() {
+        let mut stream = stream(Pubkey::new_unique(), Pubkey::new_unique(), false);
+
+        assert_eq!(locked_amount(&stream, 1_100), 0);
+        assert_eq!(locked_amount(&stream, 1_000_000), 0);
+
+        stream.amount_withdrawn = 1_000;
+        assert_eq!(locked_amount(&stream, 1_200), 0);
+    }
+
+    #[test]
+    fn cliff_vests_at_once() {
+        let mut stream = stream(Pubkey::new_unique(), Pubkey::new_unique(), false);
+
+        // 200 at the cliff, then 800 over 80s
+        stream.ix.cliff = 1_000;
+        stream.ix.cliff_amount = 200;
+        stream.end_time = 1_080;
+
+        assert_eq!(locked_amount(&stream, 999), 1_000);
+        assert_eq!(locked_amount(&stream, 1_000), 800);
+        assert_eq!(locked_amount(&stream, 1_040), 400);
+    }
+
+    fn ledger(metadata: &Pubkey) -> (Pubkey, Vec<u8>) {
+        let (key, bump) = Pubkey::find_program_address(&[b"stream_ledger", metadata.as_ref()], &crate::ID);
+        let mut data = Vec::new();
+        StreamLedger { paid: 0, last_paid_at: 0, bump: [bump] }.try_serialize(&mut data).unwrap();
+        (key, data)
+    }
+
+    #[test]
+    fn updates_the_ledgers_passed_in_stream_order() {
+        let metadata: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let (key0, mut data0) = ledger(&metadata[0]);
+        let (key2, mut data2) = ledger(&metadata[2]);
+        let (mut lamports0, mut lamports2) = (0, 0);
+        let accounts = [
+            AccountInfo::new(&key0, false, true, &mut lamports0, &mut data0, &crate::ID, false, 0),
+            AccountInfo::new(&key2, false, true, &mut lamports2, &mut data2, &crate::ID, false, 0),
+        ];
+
+        // Only the ledgers of streams 0 and 2 are passed
+        let mut ledgers = StreamLedgers::new(&accounts);
+        for (metadata, amount) in metadata.iter().zip([10, 20, 30]) {
+            ledgers.record(metadata, amount, 100).unwrap();
+        }
+        ledgers.finish().unwrap();
+
+        let paid = |info: &AccountInfo| StreamLedger::try_deserialize(&mut &info.try_borrow_data().unwrap()[..]).unwrap().paid;
+        assert_eq!(paid(&accounts[0]), 10);
+        assert_eq!(paid(&accounts[1]), 30);
+
+        // Out of order, the ledger of stream 0 is never reached
+        let mut ledgers = StreamLedgers::new(&accounts[..1]);
+        for metadata in metadata.iter().skip(1) {
+            ledgers.record(metadata, 1, 100).unwrap();
+        }
+        assert_eq!(ledgers.finish().unwrap_err(), ErrorCode::InvalidStreamLedger.into());
+    }
+
+    #[test]
+    fn keeps_the_reserve_when_carry_over_is_turned_off() {
+        let mut policy = DistributionPolicy { daily_cap: 300, carry_over: true, ..DistributionPolicy::new([0]) };
+
+        // 400 new fees owed, 300 paid, the rest carried
+        let owed = policy.investor_owed(400, 0).unwrap();
+        let reserve = policy.investor_reserve(owed, policy.investor_distributable(owed), 0, 1);
+        assert_eq!(reserve, 100);
+
+        // Turned off, the carried 100 is still owed and kept until paid, unpaid new fees aren't
+        policy.carry_over = false;
+        let owed = policy.investor_owed(250, reserve).unwrap();
+        assert_eq!(owed, 350);
+        assert_eq!(policy.investor_reserve(owed, policy.investor_distributable(owed), reserve, 1), 0);
+        assert_eq!(policy.investor_reserve(owed, 60, reserve, 1), 40);
+        assert_eq!(policy.investor_reserve(owed, 0, reserve, 1), 100);
+
+        // Nothing locked to pay it to
+        assert_eq!(policy.investor_reserve(owed, 0, reserve, 0), 0);
+    }
+
+    #[test]
+    fn keeps_the_current_stream_manager() {
+        let manager = Pubkey::new_unique();
+        let mut config = global_config(manager);
+
+        assert_eq!(config.remove_stream_sender(manager).unwrap_err(), ErrorCode::StreamSenderInUse.into());
+        assert_eq!(config.remove_stream_sender(Pubkey::new_unique()).unwrap_err(), ErrorCode::StreamSenderNotFound.into());
+        assert_eq!(config.stream_senders.len(), 2);
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Balance of a token account, read from its data so it's current after a CPI.
+pub fn balance(token_account: &AccountInfo) -> Result<u64> {
+    Ok(TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?.amount)
+}
+
+/// Sends `amount` of `mint` out of a token account owned by one of the program PDAs,
+/// signed with the PDA `signer_seeds`.
+pub fn transfer_signed<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from,
+                mint: mint.to_account_info(),
+                to,
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_interface::get_mint_extension_data;
+
+/// Fee the mint withholds on a transfer that must deliver `amount`, zero for mints without the
+/// transfer fee extension. Sending `amount` plus the fee credits the destination exactly `amount`.
+pub fn inverse_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if amount == 0 || *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+
+    let Ok(config) = get_mint_extension_data::<TransferFeeConfig>(mint) else {
+        return Ok(0);
+    };
+
+    let fee = config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(fee)
+}
+
+/// Fee the mint withholds on a transfer of `amount`, zero for mints without the transfer fee
+/// extension.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if amount == 0 || *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+
+    let Ok(config) = get_mint_extension_data::<TransferFeeConfig>(mint) else {
+        return Ok(0);
+    };
+
+    let fee = config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(fee)
+}
+
+/// Transfer paying an investor `share`
+#[derive(Clone, Copy, Default)]
+pub struct SharePayout {
+    pub share: u64,
+    // Sent to the stream, the share plus the transfer fee paid out of the treasury part
+    pub amount: u64,
+    // Left to the stream once the mint withheld its fee
+    pub received: u64,
+}
+
+impl SharePayout {
+    /// Sends the transfer fee on top of `share` out of `fee_budget`, so the stream receives the
+    /// whole share. Once the budget can't cover it, the rest of the budget is sent on top and the
+    /// stream receives what's left after the fee.
+    pub fn new(mint: &AccountInfo, share: u64, fee_budget: u64) -> Result<Self> {
+        let fee = inverse_transfer_fee(mint, share)?;
+
+        if fee <= fee_budget {
+            return Ok(SharePayout {
+                share,
+                amount: share.checked_add(fee).ok_or(ProgramError::ArithmeticOverflow)?,
+                received: share,
+            });
+        }
+
+        let amount = share.checked_add(fee_budget).ok_or(ProgramError::ArithmeticOverflow)?;
+        let received = amount.checked_sub(transfer_fee(mint, amount)?).ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(SharePayout { share, amount, received })
+    }
+
+    /// Part of the transfer fee paid out of the treasury part
+    pub fn fee_paid(&self) -> u64 {
+        self.amount - self.share
+    }
+
+    /// Transfer fee withheld by the mint
+    pub fn withheld(&self) -> u64 {
+        self.amount - self.received
+    }
+}
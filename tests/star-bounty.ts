@@ -2,8 +2,8 @@ import * as anchor from "@coral-xyz/anchor";
 import { BN, Program } from "@coral-xyz/anchor";
 import { StarBounty } from "../target/types/star_bounty";
 import { AddressLookupTableAccount, AddressLookupTableProgram, ComputeBudgetProgram, Connection, CreateLookupTableParams, ExtendLookupTableParams, Keypair, LAMPORTS_PER_SOL, PublicKey, sendAndConfirmTransaction, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction, TransactionMessage, VersionedTransaction } from "@solana/web3.js";
-import { ASSOCIATED_TOKEN_PROGRAM_ID, createAssociatedTokenAccountIdempotentInstruction, createMint, getAssociatedTokenAddressSync, Mint, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { deriveCreatorAddress, deriveInvestorFeePositionOwnerAddress, deriveMetadataAccount, derivePositionNftMintAddress } from "./star";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, createAssociatedTokenAccountIdempotentInstruction, createMint, getAssociatedTokenAddressSync, getOrCreateAssociatedTokenAccount, Mint, mintTo, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { deriveCreatorAddress, deriveCreatorFeeSplitAddress, deriveDistributionPolicyAddress, deriveDistributionStateAddress, deriveEventAuthorityAddress, deriveFeeIndexAddress, deriveFeeLedgerAddress, deriveGlobalConfigAddress, deriveInvestorFeePositionOwnerAddress, deriveLaunchConfigAddress, deriveMetadataAccount, derivePositionNftMintAddress, deriveProgramDataAddress } from "./star";
 import { DAMMV2_PROGRAM_ID, derivePoolAccount, derivePositionAccount, derivePositionNftAccount, deriveTokenVaultAccount, EVENT_AUTHORITY_ADDRESS, POOL_AUTHORITY_ADDRESS } from "./meteora";
 
 const adminSeed = [253,154,154,225,47,225,6,61,14,218,207,175,100,217,74,218,57,67,253,101,30,225,4,95,192,87,169,103,126,20,194,12,0,52,222,38,170,236,165,161,84,236,119,19,203,168,94,98,191,174,31,200,98,119,239,32,213,81,173,165,84,51,192,111];
@@ -123,7 +123,30 @@ describe("star-bounty", () => {
     await createMint(connection, adminKeypair, admin, null, 6, mintAKeypair, {skipPreflight: true})
   });
 
+  const globalConfig = deriveGlobalConfigAddress();
+
+  it("Initialize Global Config", async () => {
+    // The upgrade authority (provider wallet) picks the first admin
+    await program.methods
+      .initializeGlobalConfig(admin)
+      .accountsStrict({
+        authority: provider.publicKey,
+        globalConfig,
+        program: program.programId,
+        programData: deriveProgramDataAddress(),
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
   let creator = deriveCreatorAddress(mintB);
+  let launchConfig = deriveLaunchConfigAddress(creator);
+  let distributionPolicy = deriveDistributionPolicyAddress(creator);
+  let distributionState = deriveDistributionStateAddress(creator);
+  let starEventAuthority = deriveEventAuthorityAddress();
+  let feeIndex = deriveFeeIndexAddress(creator);
+  let feeLedger = deriveFeeLedgerAddress(creator);
+  let creatorFeeSplit = deriveCreatorFeeSplitAddress(creator);
   let positionNftMint = derivePositionNftMintAddress(creator);
   let positionNftAccount = derivePositionNftAccount(positionNftMint);
   let pool = derivePoolAccount(mintA, mintB);
@@ -134,6 +157,8 @@ describe("star-bounty", () => {
   let payerTokenA = getAssociatedTokenAddressSync(mintA, creator, true);
   let payerTokenB = getAssociatedTokenAddressSync(mintB, creator, true);
 
+  const creatorAddress = Keypair.generate().publicKey;
+
   it("Initialize Pool", async () => {
     await surfnetTokenAirdrop(connection, creator.toString(), mintA.toString(), 1_000_000_000_000_000);
     await surfnetTokenAirdrop(connection, creator.toString(), mintB.toString(), 1_000_000_000_000_000);
@@ -143,10 +168,36 @@ describe("star-bounty", () => {
     });
 
     const initializePoolIx = await program.methods
-      .initializePool()
+      .initializePool({
+        commitmentBps: 1_000,
+        poolTokenAmount: new BN(1_000_000_000_000),
+        decimals: 6,
+        streamPermissions: {
+          cancelableBySender: true,
+          cancelableByRecipient: false,
+          transferableBySender: true,
+          transferableByRecipient: false,
+        },
+        treasury: creatorAddress,
+        priceRange: { deviation: { lowerBps: 3_000, upperBps: 3_000 } },
+        poolFees: {
+          cliffFeeNumerator: new BN(2_500_000),
+          feeScheduler: null,
+          dynamicFee: null,
+        },
+        activationType: { slot: {} },
+        activationPoint: null,
+        hasAlphaVault: false,
+      })
       .accountsStrict({
         payer: admin,
+        globalConfig,
         creator,
+        launchConfig,
+        distributionPolicy,
+        distributionState,
+        feeIndex,
+        feeLedger,
         positionNftMint,
         positionNftAccount,
         poolAuthority: POOL_AUTHORITY_ADDRESS,
@@ -158,11 +209,14 @@ describe("star-bounty", () => {
         tokenBVault,
         payerTokenA,
         payerTokenB,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
         token2022Program: TOKEN_2022_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
-        eventAuthority: EVENT_AUTHORITY_ADDRESS,
+        dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
         dammv2Program: DAMMV2_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .instruction()
 
@@ -203,6 +257,20 @@ describe("star-bounty", () => {
     timelockProgram
   )[0];
 
+  const streamArgs = () => ({
+    startTime: new BN(Math.floor(Date.now() / 1000)),
+    netAmount: new BN(1_000_000),
+    period: new BN(60 * 60 * 24 * 30),
+    amountPerPeriod: new BN(1_000_000),
+    cliff: new BN(0),
+    cliffAmount: new BN(0),
+    name: Array(64).fill(0),
+    cancelableBySender: true,
+    cancelableByRecipient: false,
+    transferableBySender: true,
+    transferableByRecipient: false,
+  });
+
   it("Create Stream", async () => {
     await surfnetTokenAirdrop(connection, admin.toString(), mintB.toString(), 1_000_000_000_000_000);
 
@@ -215,10 +283,13 @@ describe("star-bounty", () => {
     ]
 
     const createStreamIx1 = await program.methods
-      .createStream()
+      .createStream(streamArgs())
       .accountsStrict({
         creator,
+        launchConfig,
+        distributionState,
         mint: mintB,
+        globalConfig,
         sender: admin,
         senderTokens,
         recipient: user,
@@ -233,14 +304,19 @@ describe("star-bounty", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .instruction()
 
       const createStreamIx2 = await program.methods
-      .createStream()
+      .createStream(streamArgs())
       .accountsStrict({
         creator,
+        launchConfig,
+        distributionState,
         mint: mintB,
+        globalConfig,
         sender: admin,
         senderTokens,
         recipient: user2,
@@ -255,14 +331,19 @@ describe("star-bounty", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .instruction()
 
       const createStreamIx3 = await program.methods
-      .createStream()
+      .createStream(streamArgs())
       .accountsStrict({
         creator,
+        launchConfig,
+        distributionState,
         mint: mintB,
+        globalConfig,
         sender: admin,
         senderTokens,
         recipient: user3,
@@ -277,14 +358,19 @@ describe("star-bounty", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .instruction()
 
       const createStreamIx4 = await program.methods
-      .createStream()
+      .createStream(streamArgs())
       .accountsStrict({
         creator,
+        launchConfig,
+        distributionState,
         mint: mintB,
+        globalConfig,
         sender: admin,
         senderTokens,
         recipient: user4,
@@ -299,6 +385,8 @@ describe("star-bounty", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .instruction()
 
@@ -321,6 +409,10 @@ describe("star-bounty", () => {
       .deposit()
       .accountsStrict({
         investorFeePosOwner: investorFeePositionOwner,
+        globalConfig,
+        creator,
+        launchConfig,
+        distributionState,
         payer: admin,
         positionNftMint: investorPositionNftMint,
         positionNftAccount: investorPositionNftAccount,
@@ -333,11 +425,14 @@ describe("star-bounty", () => {
         tokenBVault,
         payerTokenA: investorTokenA,
         payerTokenB: investorTokenB,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
         token2022Program: TOKEN_2022_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
-        eventAuthority: EVENT_AUTHORITY_ADDRESS,
+        dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
         dammv2Program: DAMMV2_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .instruction()
 
@@ -361,6 +456,9 @@ describe("star-bounty", () => {
       )
       .accountsStrict({
         payer: admin,
+        globalConfig,
+        creator,
+        launchConfig,
         poolAuthority: POOL_AUTHORITY_ADDRESS,
         pool,
         mintA,
@@ -369,8 +467,9 @@ describe("star-bounty", () => {
         tokenBVault,
         inputTokenAccount: adminTokenA,
         outputTokenAccount: adminTokenB,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        eventAuthority: EVENT_AUTHORITY_ADDRESS,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
         dammv2Program: DAMMV2_PROGRAM_ID,
       })
       .instruction()
@@ -379,7 +478,6 @@ describe("star-bounty", () => {
       await sendAndConfirmTransaction(connection, tx, [adminKeypair], {skipPreflight: true});
   });
 
-  const creatorAddress = Keypair.generate().publicKey;
   const creatorTokenAccount = getAssociatedTokenAddressSync(mintB, creatorAddress, true);
   const userTokenAccount = getAssociatedTokenAddressSync(mintB, user, true);
   const user2TokenAccount = getAssociatedTokenAddressSync(mintB, user2, true);
@@ -448,23 +546,34 @@ describe("star-bounty", () => {
     const claimFeesIx = await program.methods
       .claimFees()
       .accountsStrict({
-        mintA,
-        mintB,
-        creator,
+        signer: admin,
+        globalConfig,
+        investorPosition: {
+          mintA,
+          mintB,
+          creator,
+          investorFeePosOwner: investorFeePositionOwner,
+          positionNftMint: investorPositionNftMint,
+          positionNftAccount: investorPositionNftAccount,
+          poolAuthority: POOL_AUTHORITY_ADDRESS,
+          pool,
+          position: investorPosition,
+          tokenAVault,
+          tokenBVault,
+          tokenAAccount: investorTokenA,
+          tokenBAccount: investorTokenB,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
+          dammv2Program: DAMMV2_PROGRAM_ID,
+        },
+        launchConfig,
+        distributionPolicy,
+        distributionState,
+        feeLedger,
         creatorTokenAccount: creatorTokenAccount,
-        investorFeePosOwner: investorFeePositionOwner,
-        positionNftMint: investorPositionNftMint,
-        positionNftAccount: investorPositionNftAccount,
-        poolAuthority: POOL_AUTHORITY_ADDRESS,
-        pool,
-        position: investorPosition,
-        tokenAVault,
-        tokenBVault,
-        tokenAAccount: investorTokenA,
-        tokenBAccount: investorTokenB,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        eventAuthority: EVENT_AUTHORITY_ADDRESS,
-        dammv2Program: DAMMV2_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
       })
       .remainingAccounts([
         {
@@ -506,7 +615,7 @@ describe("star-bounty", () => {
           pubkey: user4TokenAccount,
           isWritable: true,
           isSigner: false,
-        }
+        },
       ])
       .instruction()
 
@@ -527,4 +636,460 @@ describe("star-bounty", () => {
 
     await connection.sendTransaction(tx, {skipPreflight: true});
   });
+
+  const treasuryTokenA = getAssociatedTokenAddressSync(mintA, creatorAddress, true);
+  const teamTokenA = getAssociatedTokenAddressSync(mintA, user, true);
+
+  it("Set Creator Fee Split", async () => {
+    await program.methods
+      .setCreatorFeeSplit([
+        { wallet: user, bps: 2_000 },
+      ])
+      .accountsStrict({
+        authority: admin,
+        globalConfig,
+        mint: mintB,
+        creator,
+        creatorFeeSplit,
+        systemProgram: SystemProgram.programId,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .signers([adminKeypair])
+      .rpc();
+  });
+
+  it("Claim Creator Position Fees", async () => {
+    const setupIx = [
+      createAssociatedTokenAccountIdempotentInstruction(admin, treasuryTokenA, creatorAddress, mintA),
+      createAssociatedTokenAccountIdempotentInstruction(admin, teamTokenA, user, mintA),
+    ]
+
+    const claimIx = await program.methods
+      .claimCreatorPositionFees()
+      .accountsStrict({
+        signer: admin,
+        globalConfig,
+        mintA,
+        mintB,
+        creator,
+        launchConfig,
+        creatorFeeSplit,
+        treasuryTokenA,
+        treasuryTokenB: creatorTokenAccount,
+        positionNftMint,
+        positionNftAccount,
+        poolAuthority: POOL_AUTHORITY_ADDRESS,
+        pool,
+        position,
+        tokenAVault,
+        tokenBVault,
+        tokenAAccount: payerTokenA,
+        tokenBAccount: payerTokenB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
+        dammv2Program: DAMMV2_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .remainingAccounts([
+        {
+          pubkey: teamTokenA,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: userTokenAccount,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .instruction()
+
+    const tx = new Transaction().add(...setupIx).add(claimIx);
+    await sendAndConfirmTransaction(connection, tx, [adminKeypair], {skipPreflight: true});
+  });
+
+  // Tallying only reads the locked amounts
+  const streamMetadata = () =>
+    [metadata, metadata2, metadata3, metadata4].map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }));
+
+  // No stream ledger was created, so none follow the pairs
+  const streamPages = () =>
+    [
+      [metadata, userTokenAccount],
+      [metadata2, user2TokenAccount],
+      [metadata3, user3TokenAccount],
+      [metadata4, user4TokenAccount],
+    ].flat().map((pubkey, i) => ({ pubkey, isWritable: i % 2 === 1, isSigner: false }));
+
+  it("Paginated Distribution", async () => {
+    // No dust threshold, so the epoch opens whatever the swap left in fees
+    await program.methods
+      .updateDistributionPolicy({
+        dustThreshold: new BN(0),
+        maxInvestorShareBps: 1_000,
+        dailyCap: new BN(1_000_000_000),
+        cooldown: new BN(86_400),
+        mode: { push: {} },
+        carryOver: false,
+        rounding: { floor: {} },
+      })
+      .accountsStrict({
+        admin,
+        globalConfig,
+        mint: mintB,
+        creator,
+        distributionPolicy,
+        distributionState,
+      })
+      .signers([adminKeypair])
+      .rpc();
+
+    // Past the cooldown of the claim above
+    await surfnetTimeTravel(connection, (Math.floor(Date.now() / 1000) + 2 * 86_400) * 1000);
+
+    const startDistributionIx = await program.methods
+      .startDistribution()
+      .accountsStrict({
+        signer: admin,
+        globalConfig,
+        investorPosition: {
+          mintA,
+          mintB,
+          creator,
+          investorFeePosOwner: investorFeePositionOwner,
+          positionNftMint: investorPositionNftMint,
+          positionNftAccount: investorPositionNftAccount,
+          poolAuthority: POOL_AUTHORITY_ADDRESS,
+          pool,
+          position: investorPosition,
+          tokenAVault,
+          tokenBVault,
+          tokenAAccount: investorTokenA,
+          tokenBAccount: investorTokenB,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
+          dammv2Program: DAMMV2_PROGRAM_ID,
+        },
+        launchConfig,
+        distributionPolicy,
+        distributionState,
+        feeLedger,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .instruction()
+
+    await sendAndConfirmTransaction(connection, new Transaction().add(startDistributionIx), [adminKeypair]);
+
+    let state = await program.account.distributionState.fetch(distributionState);
+    if (!state.phase.tallying) {
+      throw new Error("Distribution didn't start");
+    }
+
+    // The four streams fit in one page, tallied then paid
+    for (const phase of ["tallying", "paying"]) {
+      await program.methods
+        .distributePage(0, 4)
+        .accountsStrict({
+          signer: admin,
+          globalConfig,
+          mintB,
+          creator,
+          launchConfig,
+          distributionPolicy,
+          distributionState,
+          feeLedger,
+          investorFeePosOwner: investorFeePositionOwner,
+          tokenBAccount: investorTokenB,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: starEventAuthority,
+          program: program.programId,
+        })
+        .remainingAccounts(phase === "tallying" ? streamMetadata() : streamPages())
+        .signers([adminKeypair])
+        .rpc();
+
+      state = await program.account.distributionState.fetch(distributionState);
+      if (phase === "tallying" && !state.phase.paying) {
+        throw new Error("Tallying didn't fix the investor amount");
+      }
+    }
+
+    await program.methods
+      .finalizeDistribution()
+      .accountsStrict({
+        signer: admin,
+        globalConfig,
+        mintB,
+        creator,
+        launchConfig,
+        distributionPolicy,
+        distributionState,
+        feeLedger,
+        creatorTokenAccount,
+        investorFeePosOwner: investorFeePositionOwner,
+        tokenBAccount: investorTokenB,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .signers([adminKeypair])
+      .rpc();
+
+    state = await program.account.distributionState.fetch(distributionState);
+    if (!state.phase.idle || state.distributed.gt(state.distributable)) {
+      throw new Error("Distribution didn't finalize");
+    }
+  });
+
+  // A second launch, rewritten to the v1 layout to run the migration
+  const legacyMintKeypair = new Keypair();
+  const legacyMint = legacyMintKeypair.publicKey;
+  const legacyCreator = deriveCreatorAddress(legacyMint);
+  const legacyLaunchConfig = deriveLaunchConfigAddress(legacyCreator);
+  const legacyDistributionPolicy = deriveDistributionPolicyAddress(legacyCreator);
+  const legacyDistributionState = deriveDistributionStateAddress(legacyCreator);
+  const legacyFeeIndex = deriveFeeIndexAddress(legacyCreator);
+  const legacyFeeLedger = deriveFeeLedgerAddress(legacyCreator);
+  const legacyPositionNftMint = derivePositionNftMintAddress(legacyCreator);
+  const legacyPool = derivePoolAccount(mintA, legacyMint);
+  const legacyTokenAVault = deriveTokenVaultAccount(mintA, legacyPool);
+  const legacyTokenBVault = deriveTokenVaultAccount(legacyMint, legacyPool);
+  const legacyPayerTokenA = getAssociatedTokenAddressSync(mintA, legacyCreator, true);
+  const legacyPayerTokenB = getAssociatedTokenAddressSync(legacyMint, legacyCreator, true);
+  const legacyPositionOwner = deriveInvestorFeePositionOwnerAddress(legacyMint);
+  const legacyInvestorPositionNftMint = derivePositionNftMintAddress(legacyPositionOwner);
+  const legacyInvestorPositionNftAccount = derivePositionNftAccount(legacyInvestorPositionNftMint);
+  const legacyInvestorPosition = derivePositionAccount(legacyInvestorPositionNftMint);
+  const legacyInvestorTokenA = getAssociatedTokenAddressSync(mintA, legacyPositionOwner, true);
+  const legacyInvestorTokenB = getAssociatedTokenAddressSync(legacyMint, legacyPositionOwner, true);
+  const legacyTreasuryTokenAccount = getAssociatedTokenAddressSync(legacyMint, creatorAddress, true);
+
+  it("Set up a v1 launch", async () => {
+    await createMint(connection, adminKeypair, admin, null, 6, legacyMintKeypair, {skipPreflight: true});
+    const adminLegacyTokens = await getOrCreateAssociatedTokenAccount(connection, adminKeypair, legacyMint, admin);
+    await mintTo(connection, adminKeypair, legacyMint, adminLegacyTokens.address, adminKeypair, 1_000_000_000_000_000);
+
+    await surfnetTokenAirdrop(connection, legacyCreator.toString(), mintA.toString(), 1_000_000_000_000_000);
+    await surfnetTokenAirdrop(connection, legacyCreator.toString(), legacyMint.toString(), 1_000_000_000_000_000);
+    await surfnetTokenAirdrop(connection, legacyPositionOwner.toString(), mintA.toString(), 1_000_000_000_000_000);
+    await surfnetTokenAirdrop(connection, legacyPositionOwner.toString(), legacyMint.toString(), 1_000_000_000_000_000);
+
+    const initializePoolIx = await program.methods
+      .initializePool({
+        commitmentBps: 1_000,
+        poolTokenAmount: new BN(1_000_000_000_000),
+        decimals: 6,
+        streamPermissions: {
+          cancelableBySender: true,
+          cancelableByRecipient: false,
+          transferableBySender: true,
+          transferableByRecipient: false,
+        },
+        treasury: creatorAddress,
+        priceRange: { deviation: { lowerBps: 3_000, upperBps: 3_000 } },
+        poolFees: {
+          cliffFeeNumerator: new BN(2_500_000),
+          feeScheduler: null,
+          dynamicFee: null,
+        },
+        activationType: { slot: {} },
+        activationPoint: null,
+        hasAlphaVault: false,
+      })
+      .accountsStrict({
+        payer: admin,
+        globalConfig,
+        creator: legacyCreator,
+        launchConfig: legacyLaunchConfig,
+        distributionPolicy: legacyDistributionPolicy,
+        distributionState: legacyDistributionState,
+        feeIndex: legacyFeeIndex,
+        feeLedger: legacyFeeLedger,
+        positionNftMint: legacyPositionNftMint,
+        positionNftAccount: derivePositionNftAccount(legacyPositionNftMint),
+        poolAuthority: POOL_AUTHORITY_ADDRESS,
+        pool: legacyPool,
+        position: derivePositionAccount(legacyPositionNftMint),
+        mintA,
+        mintB: legacyMint,
+        tokenAVault: legacyTokenAVault,
+        tokenBVault: legacyTokenBVault,
+        payerTokenA: legacyPayerTokenA,
+        payerTokenB: legacyPayerTokenB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
+        dammv2Program: DAMMV2_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .instruction()
+
+    const depositIx = await program.methods
+      .deposit()
+      .accountsStrict({
+        investorFeePosOwner: legacyPositionOwner,
+        globalConfig,
+        creator: legacyCreator,
+        launchConfig: legacyLaunchConfig,
+        distributionState: legacyDistributionState,
+        payer: admin,
+        positionNftMint: legacyInvestorPositionNftMint,
+        positionNftAccount: legacyInvestorPositionNftAccount,
+        poolAuthority: POOL_AUTHORITY_ADDRESS,
+        pool: legacyPool,
+        position: legacyInvestorPosition,
+        mintA,
+        mintB: legacyMint,
+        tokenAVault: legacyTokenAVault,
+        tokenBVault: legacyTokenBVault,
+        payerTokenA: legacyInvestorTokenA,
+        payerTokenB: legacyInvestorTokenB,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
+        dammv2Program: DAMMV2_PROGRAM_ID,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .instruction()
+
+    const setComputeUnitLImitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 300_000,
+    });
+
+    await sendAndConfirmTransaction(connection, new Transaction().add(setComputeUnitLImitIx).add(initializePoolIx), [adminKeypair]);
+    await sendAndConfirmTransaction(connection, new Transaction().add(depositIx), [adminKeypair]);
+
+    // Rewrite the launch to the v1 layout: a `Creator` with a u8 stream count, the
+    // `InvestorFeePositionOwnerPda` holding the claim time and none of the launch accounts
+    const creatorDiscriminator = Buffer.from(program.idl.accounts.find((account) => account.name === "creator")!.discriminator);
+    const creatorBump = PublicKey.findProgramAddressSync([Buffer.from("creator"), legacyMint.toBuffer()], program.programId)[1];
+    const legacyCreatorData = Buffer.concat([creatorDiscriminator, Buffer.from([0, creatorBump])]);
+    await surfnetSetAccount(
+      connection,
+      legacyCreator.toString(),
+      await connection.getMinimumBalanceForRentExemption(legacyCreatorData.length),
+      legacyCreatorData.toString("hex"),
+      program.programId.toString()
+    );
+
+    const positionOwnerBump = PublicKey.findProgramAddressSync([Buffer.from("investor_fee_pos_owner"), legacyMint.toBuffer()], program.programId)[1];
+    const positionOwnerData = await program.coder.accounts.encode("investorFeePositionOwnerPda", {
+      associatedMint: legacyMint,
+      lastClaimedAt: new BN(0),
+      bump: [positionOwnerBump],
+    });
+    await surfnetSetAccount(
+      connection,
+      legacyPositionOwner.toString(),
+      await connection.getMinimumBalanceForRentExemption(positionOwnerData.length),
+      positionOwnerData.toString("hex"),
+      program.programId.toString()
+    );
+
+    for (const account of [legacyLaunchConfig, legacyDistributionPolicy, legacyDistributionState, legacyFeeIndex, legacyFeeLedger]) {
+      await surfnetSetAccount(connection, account.toString(), 0, "", SystemProgram.programId.toString());
+    }
+  });
+
+  it("Migrate Creator", async () => {
+    const migrateIx = await program.methods
+      .migrateCreator()
+      .accountsStrict({
+        payer: admin,
+        globalConfig,
+        mint: legacyMint,
+        creator: legacyCreator,
+        investorFeePosOwner: legacyPositionOwner,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction()
+
+    // No v1 stream to load with loadLegacyStreams, the launch didn't create any
+    const initializeLaunchAccountsIx = await program.methods
+      .initializeLaunchAccounts({
+        commitmentBps: 1_000,
+        poolTokenAmount: new BN(1_000_000_000_000),
+        treasury: creatorAddress,
+      })
+      .accountsStrict({
+        payer: admin,
+        globalConfig,
+        mint: legacyMint,
+        creator: legacyCreator,
+        launchConfig: legacyLaunchConfig,
+        distributionPolicy: legacyDistributionPolicy,
+        distributionState: legacyDistributionState,
+        feeIndex: legacyFeeIndex,
+        feeLedger: legacyFeeLedger,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction()
+
+    const tx = new Transaction().add(migrateIx).add(initializeLaunchAccountsIx);
+    await sendAndConfirmTransaction(connection, tx, [adminKeypair]);
+
+    const migrated = await program.account.creator.fetch(legacyCreator);
+    if (migrated.version !== 2 || migrated.positionOwnerBump[0] === 0) {
+      throw new Error("Creator not migrated");
+    }
+  });
+
+  it("Claim Fees on a migrated launch", async () => {
+    const setupIx = [
+      createAssociatedTokenAccountIdempotentInstruction(admin, legacyTreasuryTokenAccount, creatorAddress, legacyMint),
+    ]
+
+    const claimFeesIx = await program.methods
+      .claimFees()
+      .accountsStrict({
+        signer: admin,
+        globalConfig,
+        investorPosition: {
+          mintA,
+          mintB: legacyMint,
+          creator: legacyCreator,
+          investorFeePosOwner: legacyPositionOwner,
+          positionNftMint: legacyInvestorPositionNftMint,
+          positionNftAccount: legacyInvestorPositionNftAccount,
+          poolAuthority: POOL_AUTHORITY_ADDRESS,
+          pool: legacyPool,
+          position: legacyInvestorPosition,
+          tokenAVault: legacyTokenAVault,
+          tokenBVault: legacyTokenBVault,
+          tokenAAccount: legacyInvestorTokenA,
+          tokenBAccount: legacyInvestorTokenB,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          dammv2EventAuthority: EVENT_AUTHORITY_ADDRESS,
+          dammv2Program: DAMMV2_PROGRAM_ID,
+        },
+        launchConfig: legacyLaunchConfig,
+        distributionPolicy: legacyDistributionPolicy,
+        distributionState: legacyDistributionState,
+        feeLedger: legacyFeeLedger,
+        creatorTokenAccount: legacyTreasuryTokenAccount,
+        eventAuthority: starEventAuthority,
+        program: program.programId,
+      })
+      .instruction()
+
+    const tx = new Transaction().add(...setupIx).add(claimFeesIx);
+    await sendAndConfirmTransaction(connection, tx, [adminKeypair]);
+
+    const feeLedgerAccount = await program.account.feeLedger.fetch(legacyFeeLedger);
+    if (feeLedgerAccount.epochs.toNumber() !== 0) {
+      throw new Error("Dust shouldn't start an epoch");
+    }
+  });
 });
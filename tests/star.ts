@@ -3,6 +3,20 @@ import { PublicKey } from "@solana/web3.js";
 
 export const STAR_BOUNTY_PROGRAM_ID = new PublicKey("Ek8UkGyAXwg9qBPn82BEdNHjDxFHDjXhUQmRrTzmczxa");
 
+export function deriveGlobalConfigAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("global_config", "utf8")],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveProgramDataAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [STAR_BOUNTY_PROGRAM_ID.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  )[0];
+}
+
 export function deriveCreatorAddress(mint: PublicKey): PublicKey {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("creator", "utf8"), mint.toBuffer()],
@@ -10,6 +24,69 @@ export function deriveCreatorAddress(mint: PublicKey): PublicKey {
   )[0];
 }
 
+export function deriveLaunchConfigAddress(creator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("launch_config", "utf8"), creator.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveDistributionPolicyAddress(creator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("distribution_policy", "utf8"), creator.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveDistributionStateAddress(creator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("distribution", "utf8"), creator.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFeeIndexAddress(creator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_index", "utf8"), creator.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveFeeLedgerAddress(creator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_ledger", "utf8"), creator.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveCreatorFeeSplitAddress(creator: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("creator_fee_split", "utf8"), creator.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveStreamLedgerAddress(metadata: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stream_ledger", "utf8"), metadata.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveStreamCheckpointAddress(metadata: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("stream_checkpoint", "utf8"), metadata.toBuffer()],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+export function deriveEventAuthorityAddress(): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority", "utf8")],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
 export function derivePositionNftMintAddress(
     creator: PublicKey
   ): PublicKey {
@@ -20,6 +97,17 @@ export function derivePositionNftMintAddress(
   }
 
 export function deriveMetadataAccount(creator: PublicKey, stream: number): PublicKey {
+  const index = Buffer.alloc(4);
+  index.writeUInt32LE(stream);
+
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), creator.toBuffer(), index],
+    STAR_BOUNTY_PROGRAM_ID
+  )[0];
+}
+
+// Streams created before the Creator v2 migration use a single byte index
+export function deriveLegacyMetadataAccount(creator: PublicKey, stream: number): PublicKey {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("metadata"), creator.toBuffer(), Buffer.from([stream])],
     STAR_BOUNTY_PROGRAM_ID